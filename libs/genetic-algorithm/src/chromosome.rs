@@ -1,18 +1,41 @@
-use std::{ops::Index};
+use std::{
+    fmt,
+    ops::{Index, Range, RangeInclusive},
+};
 
 use rand::RngCore;
 use rand::Rng;
 
+use crate::{gene_statistics, Individual, MethodDescription};
+
 #[derive(Clone, Debug)]
 pub struct Chromosome {
     pub genes: Vec<f32>
 }
 
 impl Chromosome {
+    /// Creates a chromosome of `len` genes drawn uniformly from `range`.
+    ///
+    /// Panics if `range` is reversed or not finite.
+    pub fn random(rng: &mut dyn RngCore, len: usize, range: RangeInclusive<f32>) -> Self {
+        let (start, end) = (*range.start(), *range.end());
+
+        assert!(
+            start.is_finite() && end.is_finite() && start <= end,
+            "gene range must be finite and not reversed, got {:?}", range
+        );
+
+        (0..len).map(|_| rng.gen_range(range.clone())).collect()
+    }
+
     pub fn len(&self) -> usize {
         self.genes.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &f32> {
         self.genes.iter()
     }
@@ -20,6 +43,67 @@ impl Chromosome {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
         self.genes.iter_mut()
     }
+
+    /// Replaces the genes within `range` with `replacement`, which doesn't
+    /// have to be of the same length - the chromosome grows or shrinks
+    /// accordingly, same as with [`Vec::splice()`].
+    ///
+    /// Panics if the range starts after it ends or ends past the chromosome.
+    pub fn splice(&mut self, range: Range<usize>, replacement: &[f32]) {
+        self.genes.splice(range, replacement.iter().copied());
+    }
+
+    /// Hashes the genes rounded to multiples of `precision`, so that
+    /// near-identical chromosomes usually collide - handy for deduplicating
+    /// an archive of evolved brains.
+    ///
+    /// The hash is 64-bit FNV-1a over the gene count and the rounded genes
+    /// (as little-endian integers), so it's stable across processes, builds
+    /// and platforms - e.g. it can be stored alongside the archive. It's
+    /// meant for approximate deduplication only, though: genes lying on
+    /// opposite sides of a rounding boundary hash differently even if they're
+    /// closer than `precision`, and the hash is not cryptographic.
+    ///
+    /// Panics if `precision` isn't positive and finite.
+    pub fn quantized_hash(&self, precision: f32) -> u64 {
+        assert!(
+            precision.is_finite() && precision > 0.0,
+            "precision must be positive, got {}", precision
+        );
+
+        let mut hash = fnv1a(FNV_OFFSET_BASIS, &(self.genes.len() as u64).to_le_bytes());
+
+        for gene in &self.genes {
+            hash = fnv1a(hash, &((gene / precision).round() as i64).to_le_bytes());
+        }
+
+        hash
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Continues 64-bit FNV-1a `hash` with given bytes.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// Creates `count` individuals with random chromosomes of `chromosome_len`
+/// genes each, drawn uniformly from `range` - e.g. an initial population.
+///
+/// Panics if `range` is reversed or not finite; see [`Chromosome::random()`].
+pub fn random_population<I: Individual>(
+    rng: &mut dyn RngCore,
+    count: usize,
+    chromosome_len: usize,
+    range: RangeInclusive<f32>
+) -> Vec<I> {
+    (0..count)
+        .map(|_| I::create(Chromosome::random(rng, chromosome_len, range.clone())))
+        .collect()
 }
 
 #[cfg(test)]
@@ -64,6 +148,46 @@ mod chromosome_tests {
         Chromosome { genes: vec![3.0, 1.0, 2.0] }
     }
 
+    mod random_population {
+        use super::*;
+        use crate::TestIndividual;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        #[test]
+        fn has_requested_shape() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population: Vec<TestIndividual> = random_population(&mut rng, 7, 5, -0.5..=0.5);
+
+            assert_eq!(population.len(), 7);
+
+            for individual in &population {
+                assert_eq!(individual.chromosome().len(), 5);
+                assert!(individual.chromosome().iter().all(|gene| (-0.5..=0.5).contains(gene)));
+            }
+
+            // Everyone's different
+            assert_ne!(population[0], population[1]);
+        }
+
+        #[test]
+        fn is_reproducible() {
+            let population = |seed| -> Vec<TestIndividual> {
+                random_population(&mut ChaCha8Rng::seed_from_u64(seed), 3, 4, -1.0..=1.0)
+            };
+
+            assert_eq!(population(1), population(1));
+            assert_ne!(population(1), population(2));
+        }
+
+        #[test]
+        #[should_panic(expected = "gene range must be finite and not reversed")]
+        fn rejects_reversed_range() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let _: Vec<TestIndividual> = random_population(&mut rng, 3, 4, 1.0..=-1.0);
+        }
+    }
+
     mod into_iter {
         use super::*;
 
@@ -121,6 +245,72 @@ mod chromosome_tests {
         }
     }
 
+    mod splice {
+        use super::*;
+
+        fn chromosome() -> Chromosome {
+            Chromosome { genes: vec![1.0, 2.0, 3.0, 4.0, 5.0] }
+        }
+
+        #[test]
+        fn with_longer_replacement() {
+            let mut chromosome = chromosome();
+            chromosome.splice(1..3, &[10.0, 20.0, 30.0]);
+
+            assert_eq!(chromosome.genes, vec![1.0, 10.0, 20.0, 30.0, 4.0, 5.0]);
+        }
+
+        #[test]
+        fn with_shorter_replacement() {
+            let mut chromosome = chromosome();
+            chromosome.splice(1..4, &[10.0]);
+
+            assert_eq!(chromosome.genes, vec![1.0, 10.0, 5.0]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_range_past_the_end() {
+            chromosome().splice(3..6, &[]);
+        }
+    }
+
+    mod quantized_hash {
+        use super::*;
+
+        #[test]
+        fn near_identical_chromosomes_collide() {
+            let a = Chromosome { genes: vec![0.1002, -0.4998, 1.2] };
+            let b = Chromosome { genes: vec![0.0998, -0.5003, 1.2004] };
+
+            assert_eq!(a.quantized_hash(0.01), b.quantized_hash(0.01));
+        }
+
+        #[test]
+        fn different_chromosomes_dont_collide() {
+            let a = Chromosome { genes: vec![0.1, -0.5, 1.2] };
+            let b = Chromosome { genes: vec![0.1, -0.4, 1.2] };
+            let c = Chromosome { genes: vec![0.1, -0.5] };
+
+            assert_ne!(a.quantized_hash(0.01), b.quantized_hash(0.01));
+            assert_ne!(a.quantized_hash(0.01), c.quantized_hash(0.01));
+        }
+
+        #[test]
+        fn is_stable() {
+            let a = Chromosome { genes: vec![0.1, -0.5, 1.2] };
+
+            // FNV-1a of `3u64`, `10i64`, `-50i64` and `120i64`
+            assert_eq!(a.quantized_hash(0.01), 1041103636729710253);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_zero_precision() {
+            chromosome().quantized_hash(0.0);
+        }
+    }
+
     mod iter_mut {
         use super::*;
 
@@ -142,16 +332,22 @@ mod chromosome_tests {
 }
 
 
-pub trait CrossoverMethod {
+/// Requires `Send + Sync`, so that genetic algorithms can be shared across
+/// threads.
+pub trait CrossoverMethod: Send + Sync {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
         parent_a: &Chromosome,
         parent_b: &Chromosome
     ) -> Chromosome;
+
+    fn describe(&self) -> MethodDescription {
+        MethodDescription::of::<Self>()
+    }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct UniformCrossover;
 
 impl UniformCrossover {
@@ -176,6 +372,10 @@ impl CrossoverMethod for UniformCrossover {
             .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
             .collect()
     }
+
+    fn describe(&self) -> MethodDescription {
+        MethodDescription::new("UniformCrossover")
+    }
 }
 
 #[cfg(test)]
@@ -216,38 +416,341 @@ mod crossover_test {
     }
 }
 
+/// Crossover which swaps whole aligned blocks of `block_size` genes, e.g.
+/// all the weights of a single neuron, instead of individual genes.
+#[derive(Clone, Debug)]
+pub struct BlockCrossover {
+    block_size: usize,
+}
+
+impl BlockCrossover {
+    pub fn new(block_size: usize) -> Self {
+        assert!(block_size > 0);
+
+        Self { block_size }
+    }
+}
+
+impl CrossoverMethod for BlockCrossover {
+    /// Panics if the parents' length is not a multiple of the block size.
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+        assert_eq!(parent_a.len() % self.block_size, 0);
+
+        parent_a
+            .genes
+            .chunks(self.block_size)
+            .zip(parent_b.genes.chunks(self.block_size))
+            .flat_map(|(a, b)| if rng.gen_bool(0.5) { a } else { b })
+            .copied()
+            .collect()
+    }
+
+    fn describe(&self) -> MethodDescription {
+        MethodDescription::new("BlockCrossover")
+            .with("block_size", self.block_size as f32)
+    }
+}
+
+#[cfg(test)]
+mod block_crossover_test {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn blocks_come_from_a_single_parent() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = (1..=100)
+            .map(|n| n as f32)
+            .collect();
+
+        let parent_b: Chromosome = (1..=100)
+            .map(|n| -n as f32)
+            .collect();
+
+        let child = BlockCrossover::new(4)
+            .crossover(&mut rng, &parent_a, &parent_b);
+
+        assert_eq!(child.len(), 100);
+
+        let mut from_a = 0;
+
+        for block in child.genes.chunks(4) {
+            let all_from_a = block.iter().all(|&gene| gene > 0.0);
+            let all_from_b = block.iter().all(|&gene| gene < 0.0);
+
+            assert!(all_from_a || all_from_b);
+
+            if all_from_a {
+                from_a += 1;
+            }
+        }
+
+        assert!(from_a > 0 && from_a < 25);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_length_not_divisible_by_block_size() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+
+        BlockCrossover::new(2).crossover(&mut rng, &parent, &parent);
+    }
+}
+
+/// "Crossover" which simply copies the first parent, ignoring the second
+/// one; that is, asexual reproduction, where the offspring only differ from
+/// their parent through mutation.
+#[derive(Clone, Debug, Default)]
+pub struct CloneCrossover;
+
+impl CloneCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CrossoverMethod for CloneCrossover {
+    fn crossover(
+        &self,
+        _rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        _parent_b: &Chromosome
+    ) -> Chromosome {
+        parent_a.clone()
+    }
+
+    fn describe(&self) -> MethodDescription {
+        MethodDescription::new("CloneCrossover")
+    }
+}
+
+#[cfg(test)]
+mod clone_crossover_test {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn copies_the_first_parent() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = (1..=10).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=10).map(|n| -n as f32).collect();
+
+        let child = CloneCrossover::new().crossover(&mut rng, &parent_a, &parent_b);
+
+        assert_eq!(child.genes, parent_a.genes);
+    }
+}
+
 
-pub trait MutationMethod {
+/// Requires `Send + Sync`; see [`CrossoverMethod`].
+pub trait MutationMethod: Send + Sync {
     fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+
+    fn describe(&self) -> MethodDescription {
+        MethodDescription::of::<Self>()
+    }
 }
 
+/// Mutates each gene with probability `chance`.
+///
+/// Despite the name, a mutation created with [`GaussianMutation::new()`]
+/// perturbs the gene by a *uniform* amount in `(-coeff, coeff)`; use
+/// [`GaussianMutation::new_normal()`] for a normally distributed
+/// perturbation.
 #[derive(Clone, Debug)]
 pub struct GaussianMutation {
     chance: f32,
 
     coeff: f32,
+
+    perturbation: Perturbation,
+
+    /// Genes whose entries are `false` never get mutated.
+    mask: Option<Vec<bool>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Perturbation {
+    /// `coeff` scales a uniform sample from `[0, 1)` with a random sign.
+    Uniform,
+
+    /// `coeff` is the standard deviation of a zero-mean normal sample.
+    Normal,
 }
 
 impl GaussianMutation {
 
+    /// Panics if the parameters are invalid; see [`GaussianMutation::try_new()`].
     pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!(chance >= 0.0 && chance <= 1.0);
+        Self::try_new(chance, coeff).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        Self { chance, coeff }
+    /// Fails if `chance` lies outside of `[0, 1]` (which includes NaN) or
+    /// `coeff` is negative or not finite, so that `mutate()` never gets an
+    /// invalid probability nor poisons genes with NaN.
+    pub fn try_new(chance: f32, coeff: f32) -> Result<Self, MutationConfigError> {
+        Self::validate(chance, coeff)?;
+
+        Ok(Self { chance, coeff, perturbation: Perturbation::Uniform, mask: None })
+    }
+
+    /// Same as [`GaussianMutation::new()`], but only genes whose `mask`
+    /// entry is `true` ever get mutated - e.g. to protect the output biases.
+    ///
+    /// `mutate()` panics if the mask's length differs from the chromosome's.
+    pub fn new_masked(chance: f32, coeff: f32, mask: Vec<bool>) -> Self {
+        Self { mask: Some(mask), ..Self::new(chance, coeff) }
+    }
+
+    /// Same as [`GaussianMutation::new_masked()`], but only the biases of a
+    /// neural network with given layer sizes (from the inputs to the
+    /// outputs) get mutated, while its weights stay fixed.
+    ///
+    /// Genes are expected to follow the neural network's layout, where each
+    /// neuron contributes its bias followed by one weight per input.
+    pub fn biases_only(chance: f32, coeff: f32, layers: &[usize]) -> Self {
+        let mask = layers
+            .windows(2)
+            .flat_map(|layers| {
+                let (inputs, neurons) = (layers[0], layers[1]);
+
+                (0..neurons).flat_map(move |_| {
+                    std::iter::once(true).chain(std::iter::repeat_n(false, inputs))
+                })
+            })
+            .collect();
+
+        Self::new_masked(chance, coeff, mask)
+    }
+
+    /// Same as [`GaussianMutation::new()`], but with `coeff` set to `scale`
+    /// times the genes' standard deviation across the population, averaged
+    /// over all the genes (zero for an empty population).
+    ///
+    /// The coefficient is computed once, here - it doesn't follow the
+    /// population's spread as it evolves.
+    ///
+    /// Panics if the chromosomes have different lengths, or if the
+    /// parameters are invalid.
+    pub fn from_population<I: Individual>(chance: f32, population: &[I], scale: f32) -> Self {
+        let stats = gene_statistics(population);
+
+        let mean_std = if stats.is_empty() {
+            0.0
+        } else {
+            stats.iter().map(|stat| stat.variance.sqrt()).sum::<f32>() / stats.len() as f32
+        };
+
+        Self::new(chance, scale * mean_std)
+    }
+
+    /// Creates a mutation which adds a sample from `N(0, std²)` to each
+    /// mutated gene.
+    pub fn new_normal(chance: f32, std: f32) -> Self {
+        if let Err(err) = Self::validate(chance, std) {
+            panic!("{}", err);
+        }
+
+        Self { chance, coeff: std, perturbation: Perturbation::Normal, mask: None }
+    }
+
+    fn validate(chance: f32, coeff: f32) -> Result<(), MutationConfigError> {
+        // NaN isn't contained in any range, so it's rejected here as well
+        if !(0.0..=1.0).contains(&chance) {
+            return Err(MutationConfigError::ChanceOutOfRange { chance });
+        }
+
+        if !(coeff.is_finite() && coeff >= 0.0) {
+            return Err(MutationConfigError::InvalidCoeff { coeff });
+        }
+
+        Ok(())
     }
     
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum MutationConfigError {
+    ChanceOutOfRange { chance: f32 },
+    InvalidCoeff { coeff: f32 },
+}
+
+impl fmt::Display for MutationConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChanceOutOfRange { chance } => {
+                write!(f, "mutation chance must be within [0, 1], got {}", chance)
+            }
+
+            Self::InvalidCoeff { coeff } => {
+                write!(f, "mutation coefficient must be finite and non-negative, got {}", coeff)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MutationConfigError {}
+
 impl MutationMethod for GaussianMutation {
     fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
-        for gene in child.iter_mut() {
-            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+        if let Some(mask) = &self.mask {
+            assert_eq!(mask.len(), child.len(), "mutation mask doesn't match the chromosome");
+        }
 
-            if rng.gen_bool(self.chance as _) {
-                *gene += sign * self.coeff * rng.gen::<f32>();
+        for (id, gene) in child.iter_mut().enumerate() {
+            if self.mask.as_ref().is_some_and(|mask| !mask[id]) {
+                continue;
+            }
+
+            match self.perturbation {
+                Perturbation::Uniform => {
+                    let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+
+                    if rng.gen_bool(self.chance as _) {
+                        *gene += sign * self.coeff * rng.gen::<f32>();
+                    }
+                }
+
+                Perturbation::Normal => {
+                    if rng.gen_bool(self.chance as _) {
+                        *gene += self.coeff * standard_normal(rng);
+                    }
+                }
             }
         }
     }
+
+    fn describe(&self) -> MethodDescription {
+        let description = MethodDescription::new("GaussianMutation")
+            .with("chance", self.chance);
+
+        match self.perturbation {
+            Perturbation::Uniform => description.with("coeff", self.coeff),
+            Perturbation::Normal => description.with("std", self.coeff),
+        }
+    }
+}
+
+/// Samples `N(0, 1)` using the Box-Muller transform; used for
+/// [`GaussianMutation`]'s normal perturbation, but handy for any Gaussian
+/// noise.
+pub fn standard_normal(rng: &mut dyn RngCore) -> f32 {
+    // `1.0 - gen()` lies in `(0, 1]`, which keeps `ln()` finite
+    let u1 = 1.0 - rng.gen::<f32>();
+    let u2 = rng.gen::<f32>();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
 }
 
 #[cfg(test)]
@@ -306,4 +809,178 @@ mod tests {
         }
 
     }
-}
\ No newline at end of file
+
+    mod try_new {
+        use super::*;
+
+        #[test]
+        fn accepts_valid_parameters() {
+            assert!(GaussianMutation::try_new(0.5, 0.1).is_ok());
+            assert!(GaussianMutation::try_new(1.0, 0.0).is_ok());
+        }
+
+        #[test]
+        fn rejects_too_high_chance() {
+            let err = GaussianMutation::try_new(1.5, 0.1).unwrap_err();
+
+            assert_eq!(err, MutationConfigError::ChanceOutOfRange { chance: 1.5 });
+        }
+
+        #[test]
+        fn rejects_too_low_chance() {
+            let err = GaussianMutation::try_new(-0.1, 0.1).unwrap_err();
+
+            assert_eq!(err, MutationConfigError::ChanceOutOfRange { chance: -0.1 });
+        }
+
+        #[test]
+        fn rejects_nan_chance() {
+            let err = GaussianMutation::try_new(f32::NAN, 0.1).unwrap_err();
+
+            assert!(matches!(err, MutationConfigError::ChanceOutOfRange { chance } if chance.is_nan()));
+        }
+
+        #[test]
+        #[should_panic(expected = "mutation chance must be within [0, 1], got NaN")]
+        fn new_normal_panics_on_nan_chance_at_construction() {
+            GaussianMutation::new_normal(f32::NAN, 0.1);
+        }
+
+        #[test]
+        fn rejects_negative_coeff() {
+            let err = GaussianMutation::try_new(0.5, -0.1).unwrap_err();
+
+            assert_eq!(err, MutationConfigError::InvalidCoeff { coeff: -0.1 });
+        }
+
+        #[test]
+        fn rejects_non_finite_coeff() {
+            let err = GaussianMutation::try_new(0.5, f32::NAN).unwrap_err();
+
+            assert!(matches!(err, MutationConfigError::InvalidCoeff { coeff } if coeff.is_nan()));
+
+            let err = GaussianMutation::try_new(0.5, f32::INFINITY).unwrap_err();
+
+            assert_eq!(err, MutationConfigError::InvalidCoeff { coeff: f32::INFINITY });
+        }
+
+        #[test]
+        #[should_panic(expected = "mutation coefficient must be finite and non-negative, got NaN")]
+        fn new_normal_panics_on_nan_std_at_construction() {
+            GaussianMutation::new_normal(0.5, f32::NAN);
+        }
+
+        #[test]
+        #[should_panic(expected = "mutation chance must be within [0, 1]")]
+        fn new_panics_on_invalid_parameters() {
+            GaussianMutation::new(2.0, 0.1);
+        }
+    }
+
+    mod given_normal_distribution {
+        use super::*;
+
+        #[test]
+        fn deltas_have_zero_mean_and_expected_std() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![0.0; 10_000].into_iter().collect();
+
+            GaussianMutation::new_normal(1.0, 0.5)
+                .mutate(&mut rng, &mut child);
+
+            let n = child.len() as f32;
+            let mean = child.iter().sum::<f32>() / n;
+            let variance = child.iter().map(|delta| (delta - mean).powi(2)).sum::<f32>() / n;
+
+            approx::assert_abs_diff_eq!(mean, 0.0, epsilon = 0.02);
+            approx::assert_abs_diff_eq!(variance.sqrt(), 0.5, epsilon = 0.02);
+        }
+
+        #[test]
+        fn zero_chance_does_not_change_the_original_chromosome() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+
+            GaussianMutation::new_normal(0.0, 0.5)
+                .mutate(&mut rng, &mut child);
+
+            let expected: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+            assert_eq!(child, expected);
+        }
+    }
+
+    mod given_mask {
+        use super::*;
+
+        #[test]
+        fn masked_genes_are_untouched() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![1.0; 6].into_iter().collect();
+            let mask = vec![true, false, true, false, true, false];
+
+            GaussianMutation::new_masked(1.0, 0.5, mask.clone())
+                .mutate(&mut rng, &mut child);
+
+            for (gene, mutable) in child.iter().zip(mask) {
+                assert_eq!(*gene != 1.0, mutable);
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "mutation mask doesn't match the chromosome")]
+        fn rejects_mask_of_different_length() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![1.0; 3].into_iter().collect();
+
+            GaussianMutation::new_masked(1.0, 0.5, vec![true; 2])
+                .mutate(&mut rng, &mut child);
+        }
+    }
+
+    mod biases_only {
+        use super::*;
+
+        #[test]
+        fn only_biases_change() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Layers of 2, 2 and 1 neurons: three neurons, each with a bias
+            // followed by two weights
+            let mut child: Chromosome = vec![1.0; 9].into_iter().collect();
+
+            GaussianMutation::biases_only(1.0, 0.5, &[2, 2, 1])
+                .mutate(&mut rng, &mut child);
+
+            for (id, gene) in child.iter().enumerate() {
+                assert_eq!(*gene != 1.0, id % 3 == 0, "gene {}", id);
+            }
+        }
+    }
+
+    mod from_population {
+        use super::*;
+        use crate::TestIndividual;
+
+        fn individual(genes: &[f32]) -> TestIndividual {
+            TestIndividual::create(genes.iter().cloned().collect())
+        }
+
+        #[test]
+        fn scales_the_mean_gene_std() {
+            // The first gene's std is 1.0, the second one's is 2.0
+            let population = vec![individual(&[0.0, 10.0]), individual(&[2.0, 14.0])];
+
+            let mutation = GaussianMutation::from_population(0.5, &population, 2.0);
+
+            approx::assert_relative_eq!(mutation.coeff, 3.0);
+            assert_eq!(mutation.chance, 0.5);
+        }
+
+        #[test]
+        fn empty_population_gives_zero_coeff() {
+            let mutation = GaussianMutation::from_population::<TestIndividual>(0.5, &[], 2.0);
+
+            assert_eq!(mutation.coeff, 0.0);
+        }
+    }
+}
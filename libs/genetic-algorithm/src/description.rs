@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Which operators a [`GeneticAlgorithm`](crate::GeneticAlgorithm) uses,
+/// suitable for recording alongside experiment results.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GaDescription {
+    pub selection: MethodDescription,
+    pub crossover: MethodDescription,
+    pub mutation: MethodDescription,
+}
+
+/// Name and parameters of a single operator.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MethodDescription {
+    pub name: String,
+    pub parameters: BTreeMap<String, f32>,
+}
+
+impl MethodDescription {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), parameters: BTreeMap::new() }
+    }
+
+    /// Describes a method by its type name, without any parameters.
+    pub fn of<T: ?Sized>() -> Self {
+        let name = std::any::type_name::<T>();
+        let name = name.split('<').next().unwrap_or(name);
+        let name = name.rsplit("::").next().unwrap_or(name);
+
+        Self::new(name)
+    }
+
+    pub fn with(mut self, parameter: impl Into<String>, value: f32) -> Self {
+        self.parameters.insert(parameter.into(), value);
+        self
+    }
+}
@@ -0,0 +1,129 @@
+use crate::*;
+
+/// Creates an initial population out of given generator.
+pub type PopulationFactory<I> = Box<dyn Fn(&mut dyn RngCore) -> Vec<I>>;
+
+/// Everything needed to repeat a run: the genetic algorithm and a factory
+/// creating the initial population out of the run's generator.
+pub struct ExperimentConfig<S, I> {
+    ga: GeneticAlgorithm<S>,
+    population: PopulationFactory<I>,
+}
+
+impl<S, I> ExperimentConfig<S, I> {
+    pub fn new(
+        ga: GeneticAlgorithm<S>,
+        population: impl Fn(&mut dyn RngCore) -> Vec<I> + 'static,
+    ) -> Self {
+        Self { ga, population: Box::new(population) }
+    }
+}
+
+/// Outcome of [`experiment()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExperimentResult {
+    /// Best fitness of the final population of each run, in the order of
+    /// the seeds.
+    pub best_fitness: Vec<f32>,
+    pub mean: f32,
+    /// Population standard deviation, i.e. divided by the number of runs.
+    pub std: f32,
+}
+
+/// Runs the configured genetic algorithm once per seed, each time for
+/// `generations` generations and from a population created by a generator
+/// seeded with that seed, and sums up how fit the best individuals got.
+///
+/// Since nothing is evaluated in between generations, the individuals have
+/// to derive their fitness from their chromosomes alone.
+///
+/// Panics if there are no seeds.
+pub fn experiment<S, I>(
+    config: &ExperimentConfig<S, I>,
+    seeds: &[u64],
+    generations: usize,
+) -> ExperimentResult
+where
+    S: SelectionMethod,
+    I: Individual,
+{
+    assert!(!seeds.is_empty(), "experiment needs at least one seed");
+
+    let best_fitness: Vec<f32> = seeds
+        .iter()
+        .map(|&seed| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let population = (config.population)(&mut rng);
+
+            config
+                .ga
+                .run_with_callback(&mut rng, &population, generations, |_, _, _| {})
+                .iter()
+                .map(Individual::fitness)
+                .fold(f32::NEG_INFINITY, f32::max)
+        })
+        .collect();
+
+    let n = best_fitness.len() as f32;
+    let mean = best_fitness.iter().sum::<f32>() / n;
+    let std = (best_fitness.iter().map(|f| (f - mean).powi(2)).sum::<f32>() / n).sqrt();
+
+    ExperimentResult { best_fitness, mean, std }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn config() -> ExperimentConfig<RouletteWheelSelection, TestIndividual> {
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        ExperimentConfig::new(ga, |rng| {
+            (0..10)
+                .map(|_| TestIndividual::create((0..3).map(|_| rng.gen::<f32>()).collect()))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn is_reproducible() {
+        let seeds = [1, 2, 3, 4];
+
+        let a = experiment(&config(), &seeds, 5);
+        let b = experiment(&config(), &seeds, 5);
+
+        assert_eq!(a, b);
+        assert_eq!(a.best_fitness.len(), 4);
+    }
+
+    #[test]
+    fn aggregates_the_runs() {
+        let result = experiment(&config(), &[1, 2, 3], 5);
+
+        let single: Vec<_> = [1, 2, 3]
+            .into_iter()
+            .map(|seed| experiment(&config(), &[seed], 5))
+            .collect();
+
+        for (best, single) in result.best_fitness.iter().zip(&single) {
+            assert_eq!(single.best_fitness, vec![*best]);
+            assert_eq!(single.mean, *best);
+            assert_eq!(single.std, 0.0);
+        }
+
+        let mean = result.best_fitness.iter().sum::<f32>() / 3.0;
+        approx::assert_relative_eq!(result.mean, mean);
+        assert!(result.std > 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one seed")]
+    fn rejects_no_seeds() {
+        experiment(&config(), &[], 5);
+    }
+}
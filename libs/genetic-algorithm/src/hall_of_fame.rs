@@ -0,0 +1,103 @@
+use crate::*;
+
+/// Keeps the fittest individuals seen across all the generations it's been
+/// shown, so that the best ones survive even if later generations regress.
+#[derive(Clone, Debug)]
+pub struct HallOfFame<I> {
+    capacity: usize,
+    /// Fittest first.
+    members: Vec<I>,
+}
+
+impl<I> HallOfFame<I>
+where
+    I: Individual + Clone,
+{
+    /// Creates an empty hall of fame that keeps up to `capacity`
+    /// individuals.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, members: Vec::with_capacity(capacity) }
+    }
+
+    /// Admits the population's fittest individuals, evicting whoever's no
+    /// longer among the `capacity` fittest seen so far.
+    ///
+    /// Fitness is compared with [`f32::total_cmp()`]; on ties, the earlier
+    /// members stay, and newcomers are admitted in their population order
+    /// (see [`top_k()`]).
+    pub fn consider(&mut self, population: &[I]) {
+        let candidates = top_k(population, self.capacity);
+
+        self.members.extend(candidates.into_iter().cloned());
+        self.members.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+        self.members.truncate(self.capacity);
+    }
+
+    /// The fittest individual seen so far, if any.
+    pub fn best(&self) -> Option<&I> {
+        self.members.first()
+    }
+
+    /// Everyone currently in the hall of fame, fittest first.
+    pub fn members(&self) -> &[I] {
+        &self.members
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fitness(hall: &HallOfFame<TestIndividual>) -> Vec<f32> {
+        hall.members().iter().map(Individual::fitness).collect()
+    }
+
+    #[test]
+    fn keeps_the_all_time_best_of_a_declining_population() {
+        let mut hall = HallOfFame::new(2);
+
+        for generation in 0..5 {
+            let best = 10.0 - generation as f32;
+
+            let population = vec![
+                TestIndividual::new(best - 3.0),
+                TestIndividual::new(best),
+                TestIndividual::new(best - 1.5),
+            ];
+
+            hall.consider(&population);
+        }
+
+        assert_eq!(hall.best(), Some(&TestIndividual::new(10.0)));
+        assert_eq!(fitness(&hall), vec![10.0, 9.0]);
+    }
+
+    #[test]
+    fn admits_better_newcomers() {
+        let mut hall = HallOfFame::new(3);
+
+        hall.consider(&[TestIndividual::new(1.0), TestIndividual::new(2.0)]);
+        assert_eq!(fitness(&hall), vec![2.0, 1.0]);
+
+        hall.consider(&[TestIndividual::new(5.0), TestIndividual::new(0.5)]);
+        assert_eq!(fitness(&hall), vec![5.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn empty_hall_has_no_best() {
+        let hall = HallOfFame::<TestIndividual>::new(3);
+        assert_eq!(hall.best(), None);
+    }
+
+    #[test]
+    fn zero_capacity_keeps_no_one() {
+        let mut hall = HallOfFame::new(0);
+        hall.consider(&[TestIndividual::new(1.0)]);
+
+        assert!(hall.members().is_empty());
+    }
+}
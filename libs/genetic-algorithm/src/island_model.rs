@@ -0,0 +1,147 @@
+use crate::*;
+
+/// Several populations evolving in isolation from each other, each with its
+/// own genetic algorithm, occasionally exchanging their fittest individuals
+/// (migrants).
+///
+/// The islands form a ring: migrants leave island `i` for island `i + 1`,
+/// and the last island's migrants go to the first one.
+pub struct IslandModel<S, I> {
+    islands: Vec<(GeneticAlgorithm<S>, Vec<I>)>,
+}
+
+impl<S, I> IslandModel<S, I>
+where
+    S: SelectionMethod,
+    I: Individual + Clone,
+{
+    pub fn new(islands: Vec<(GeneticAlgorithm<S>, Vec<I>)>) -> Self {
+        Self { islands }
+    }
+
+    pub fn len(&self) -> usize {
+        self.islands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.islands.is_empty()
+    }
+
+    /// Returns given island's population; panics if there's no such island.
+    pub fn population(&self, island: usize) -> &[I] {
+        &self.islands[island].1
+    }
+
+    /// Evolves each island's population by a single generation, using that
+    /// island's genetic algorithm.
+    pub fn evolve_all(&mut self, rng: &mut dyn RngCore) {
+        for (ga, population) in &mut self.islands {
+            *population = ga.evolve(rng, population);
+        }
+    }
+
+    /// Copies each island's `rate` fittest individuals to the next island,
+    /// where they replace its `rate` least fit ones; islands with fewer than
+    /// `rate` individuals exchange everyone they have.
+    pub fn migrate(&mut self, rate: usize) {
+        let migrants: Vec<Vec<I>> = self
+            .islands
+            .iter()
+            .map(|(_, population)| top_k(population, rate).into_iter().cloned().collect())
+            .collect();
+
+        let count = self.islands.len();
+
+        for (from, migrants) in migrants.into_iter().enumerate() {
+            let population = &mut self.islands[(from + 1) % count].1;
+
+            let mut weakest: Vec<_> = (0..population.len()).collect();
+
+            weakest.sort_by(|&a, &b| {
+                population[a].fitness().total_cmp(&population[b].fitness())
+            });
+
+            for (id, migrant) in weakest.into_iter().zip(migrants) {
+                population[id] = migrant;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn ga() -> GeneticAlgorithm<RouletteWheelSelection> {
+        GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+    }
+
+    fn fitness(population: &[TestIndividual]) -> Vec<f32> {
+        population.iter().map(Individual::fitness).collect()
+    }
+
+    mod migrate {
+        use super::*;
+
+        #[test]
+        fn moves_the_best_individuals_to_the_next_island() {
+            let mut islands = IslandModel::new(vec![
+                (ga(), vec![TestIndividual::new(10.0), TestIndividual::new(2.0)]),
+                (ga(), vec![TestIndividual::new(1.0), TestIndividual::new(3.0)]),
+            ]);
+
+            islands.migrate(1);
+
+            assert_eq!(fitness(islands.population(0)), vec![10.0, 3.0]);
+            assert_eq!(fitness(islands.population(1)), vec![10.0, 3.0]);
+        }
+
+        #[test]
+        fn follows_the_ring() {
+            let mut islands = IslandModel::new(vec![
+                (ga(), vec![TestIndividual::new(1.0), TestIndividual::new(0.0)]),
+                (ga(), vec![TestIndividual::new(2.0), TestIndividual::new(0.0)]),
+                (ga(), vec![TestIndividual::new(3.0), TestIndividual::new(0.0)]),
+            ]);
+
+            islands.migrate(1);
+
+            assert_eq!(fitness(islands.population(0)), vec![1.0, 3.0]);
+            assert_eq!(fitness(islands.population(1)), vec![2.0, 1.0]);
+            assert_eq!(fitness(islands.population(2)), vec![3.0, 2.0]);
+        }
+    }
+
+    mod evolve_all {
+        use super::*;
+
+        fn individual(genes: &[f32]) -> TestIndividual {
+            TestIndividual::create(genes.iter().cloned().collect())
+        }
+
+        #[test]
+        fn evolves_each_island_separately() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut islands = IslandModel::new(vec![
+                (ga(), vec![individual(&[0.0, 0.0]); 3]),
+                (ga(), vec![individual(&[100.0, 100.0]); 4]),
+            ]);
+
+            islands.evolve_all(&mut rng);
+
+            assert_eq!(islands.population(0).len(), 3);
+            assert_eq!(islands.population(1).len(), 4);
+
+            // Without migration, the islands' genes stay far apart
+            assert!(fitness(islands.population(0)).iter().all(|&fitness| fitness < 50.0));
+            assert!(fitness(islands.population(1)).iter().all(|&fitness| fitness > 150.0));
+        }
+    }
+}
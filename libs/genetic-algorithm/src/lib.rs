@@ -1,25 +1,59 @@
-use rand::RngCore;
-use rand::seq::SliceRandom;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 mod chromosome;
+mod description;
+mod experiment;
+mod hall_of_fame;
+mod island_model;
+mod objectives;
+mod prepared_selection;
+mod statistics;
+mod timings;
 
 pub use self:: {
-    chromosome::*
+    chromosome::*,
+    description::*,
+    experiment::*,
+    hall_of_fame::*,
+    island_model::*,
+    objectives::*,
+    prepared_selection::*,
+    statistics::*,
+    timings::Timings
 };
 
+/// Cloning is cheap, since the crossover and mutation methods are shared
+/// between the clones.
+#[derive(Clone)]
 pub struct GeneticAlgorithm<S> {
     selection_method: S,
-    crossover_method: Box<dyn CrossoverMethod>,
-    mutation_method: Box<dyn MutationMethod>
+    crossover_method: Arc<dyn CrossoverMethod>,
+    mutation_method: Arc<dyn MutationMethod>,
+    elitism: usize,
+    crossover_rate: f32,
+    gene_bounds: Option<RangeInclusive<f32>>
 }
 
 pub trait Individual {
     fn create(chromosome: Chromosome) -> Self;
     fn fitness(&self) -> f32;
     fn chromosome(&self) -> &Chromosome;
+
+    /// Returns the chromosome's length, or `None` if this individual doesn't
+    /// carry a chromosome (in which case `chromosome()` would panic).
+    ///
+    /// Individuals that always have a chromosome don't need to override it.
+    fn chromosome_len(&self) -> Option<usize> {
+        Some(self.chromosome().len())
+    }
 }
 
-pub trait SelectionMethod {
+/// Requires `Send + Sync`; see [`CrossoverMethod`].
+pub trait SelectionMethod: Send + Sync {
     fn select<'a, I>(
         &self, 
         rng: &mut dyn RngCore,
@@ -27,6 +61,46 @@ pub trait SelectionMethod {
     ) -> &'a I
     where
         I: Individual;
+
+    /// Precomputes whatever's needed to make repeated selections from the
+    /// same population cheaper; `evolve()` uses it when available, falling
+    /// back to `select()` otherwise.
+    ///
+    /// Selecting through the returned value has to behave exactly like
+    /// `select()` does.
+    fn prepare<'a, I>(&self, _population: &'a [I]) -> Option<PreparedSelection<'a, I>>
+    where
+        I: Individual,
+    {
+        None
+    }
+
+    /// Returns how likely `select()` is to pick each individual; the
+    /// probabilities sum up to one.
+    ///
+    /// Defaults to picking everyone with the same probability.
+    fn probabilities<I>(&self, population: &[I]) -> Vec<f32>
+    where
+        I: Individual,
+    {
+        vec![1.0 / population.len() as f32; population.len()]
+    }
+
+    fn describe(&self) -> MethodDescription {
+        MethodDescription::of::<Self>()
+    }
+}
+
+/// Scales weights so that they sum up to one; if they're all zero, gives
+/// everyone the same share instead.
+fn weight_shares(weights: Vec<f32>) -> Vec<f32> {
+    let total: f32 = weights.iter().sum();
+
+    if total > 0.0 {
+        weights.into_iter().map(|weight| weight / total).collect()
+    } else {
+        vec![1.0 / weights.len() as f32; weights.len()]
+    }
 }
 
 impl<S> GeneticAlgorithm<S>
@@ -40,44 +114,199 @@ where
     ) -> Self {
         Self { 
             selection_method,
-            crossover_method: Box::new(crossover_method),
-            mutation_method: Box::new(mutation_method)
+            crossover_method: Arc::new(crossover_method),
+            mutation_method: Arc::new(mutation_method),
+            elitism: 0,
+            crossover_rate: 1.0,
+            gene_bounds: None
          }
     }
 
+    /// Makes `evolve()` carry the `elitism` fittest individuals over to the
+    /// next generation unchanged (before any offspring).
+    ///
+    /// The elites are ordered by [`f32::total_cmp()`], with ties broken by
+    /// their positions in the population, earlier first - so that seeded
+    /// runs don't depend on how the sorting happens to be implemented.
+    pub fn with_elitism(mut self, elitism: usize) -> Self {
+        self.elitism = elitism;
+        self
+    }
+
+    /// Makes `evolve()` cross the parents over only with probability
+    /// `crossover_rate`; otherwise the child starts as a copy of the first
+    /// parent. Either way, the child gets mutated afterwards.
+    ///
+    /// Panics if `crossover_rate` lies outside of `[0, 1]`.
+    pub fn with_crossover_rate(mut self, crossover_rate: f32) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&crossover_rate),
+            "crossover rate must be within [0, 1], got {}", crossover_rate
+        );
+
+        self.crossover_rate = crossover_rate;
+        self
+    }
+
+    /// Makes `evolve()` clamp every gene of every offspring into `bounds`,
+    /// after crossover and mutation; elites are carried over as they are.
+    ///
+    /// Panics if the bounds are empty.
+    pub fn with_gene_bounds(mut self, bounds: RangeInclusive<f32>) -> Self {
+        assert!(
+            bounds.start() <= bounds.end(),
+            "gene bounds must not be empty, got {:?}", bounds
+        );
+
+        self.gene_bounds = Some(bounds);
+        self
+    }
+
+    pub fn describe(&self) -> GaDescription {
+        let mut crossover = self.crossover_method.describe();
+
+        if self.crossover_rate < 1.0 {
+            crossover = crossover.with("rate", self.crossover_rate);
+        }
+
+        GaDescription {
+            selection: self.selection_method.describe(),
+            crossover,
+            mutation: self.mutation_method.describe(),
+        }
+    }
+
+    /// Evolves the population for given number of generations, drawing all
+    /// the randomness from a generator seeded with `seed` - so that the same
+    /// seed always yields the same final population.
+    ///
+    /// Since nothing is evaluated in between generations, the individuals
+    /// have to derive their fitness from their chromosomes alone.
+    pub fn run_seeded<I>(&self, seed: u64, population: &[I], generations: usize) -> Vec<I>
+    where
+        I: Individual,
+    {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        self.run_with_callback(&mut rng, population, generations, |_, _, _| {})
+    }
+
+    /// Evolves the population for given number of generations, calling
+    /// `callback` after each one with the number of generations completed so
+    /// far, the new population and how long each operator took.
+    ///
+    /// Since nothing is evaluated in between generations, the individuals
+    /// have to derive their fitness from their chromosomes alone.
+    pub fn run_with_callback<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        generations: usize,
+        mut callback: impl FnMut(usize, &[I], &Timings)
+    ) -> Vec<I>
+    where
+        I: Individual,
+    {
+        let mut population: Vec<_> = population
+            .iter()
+            .map(|individual| I::create(individual.chromosome().clone()))
+            .collect();
+
+        for generation in 1..=generations {
+            let mut timings = Timings::default();
+
+            population = self.evolve_timed(rng, &population, &mut timings);
+            callback(generation, &population, &timings);
+        }
+
+        population
+    }
+
+    /// Breeds a new population of the same size; an empty population
+    /// evolves into an empty one.
     pub fn evolve<I>(
         &self,
         rng: &mut dyn RngCore,
         population: &[I]
     ) -> Vec<I>
+    where
+        I: Individual,
+    {
+        self.evolve_timed(rng, population, &mut Timings::default())
+    }
+
+    fn evolve_timed<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        timings: &mut Timings
+    ) -> Vec<I>
     where
         I: Individual,
         {
-            (0..population.len())
+            // Selection needs someone to select from
+            if population.is_empty() {
+                return Vec::new();
+            }
+
+            let mut fittest: Vec<_> = population.iter().collect();
+
+            if self.elitism > 0 {
+                // Stable, so equally fit individuals keep their order
+                fittest.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+            }
+
+            let elites = fittest
+                .into_iter()
+                .take(self.elitism)
+                .map(|individual| I::create(individual.chromosome().clone()));
+
+            let prepared = timings::measure(&mut timings.selection, || {
+                self.selection_method.prepare(population)
+            });
+
+            let select = |rng: &mut dyn RngCore| match &prepared {
+                Some(prepared) => prepared.select(rng),
+                None => self.selection_method.select(rng, population),
+            };
+
+            let offspring = (elites.len()..population.len())
                 .map(|_| {
-                    let parent_a = self
-                        .selection_method
-                        .select(rng, population)
-                        .chromosome();
-
-                    let parent_b = self
-                        .selection_method
-                        .select(rng, population)
-                        .chromosome();
-                    
-                    let mut child = self
-                        .crossover_method
-                        .crossover(rng, parent_a, parent_b);
-                    
-                    self.mutation_method.mutate(rng, &mut child);
+                    let (parent_a, parent_b) = timings::measure(&mut timings.selection, || {
+                        (select(rng).chromosome(), select(rng).chromosome())
+                    });
+
+                    let mut child = timings::measure(&mut timings.crossover, || {
+                        // Always crossing over doesn't need to draw anything
+                        let cross = self.crossover_rate >= 1.0
+                            || rng.gen_bool(self.crossover_rate as _);
+
+                        if cross {
+                            self.crossover_method.crossover(rng, parent_a, parent_b)
+                        } else {
+                            parent_a.clone()
+                        }
+                    });
+
+                    timings::measure(&mut timings.mutation, || {
+                        self.mutation_method.mutate(rng, &mut child)
+                    });
+
+                    if let Some(bounds) = &self.gene_bounds {
+                        for gene in child.iter_mut() {
+                            *gene = gene.clamp(*bounds.start(), *bounds.end());
+                        }
+                    }
 
                     I::create(child)
-                })
-                .collect()
+                });
+
+            elites.chain(offspring).collect()
         }
 
 }
 
+#[derive(Clone, Debug, Default)]
 pub struct RouletteWheelSelection;
 
 impl RouletteWheelSelection {
@@ -87,6 +316,8 @@ impl RouletteWheelSelection {
 }
 
 impl SelectionMethod for RouletteWheelSelection {
+    /// Picks individuals proportionally to their fitness; if everyone's
+    /// fitness is zero, picks uniformly instead.
     fn select<'a, I>(
         &self, 
         rng: &mut dyn RngCore,
@@ -95,8 +326,92 @@ impl SelectionMethod for RouletteWheelSelection {
     where 
         I: Individual, 
     {
-        population.choose_weighted(rng, |individual| individual.fitness())
-            .expect("got an empty population")
+        self.prepare(population)
+            .expect("roulette can always be prepared")
+            .select(rng)
+    }
+
+    fn prepare<'a, I>(&self, population: &'a [I]) -> Option<PreparedSelection<'a, I>>
+    where
+        I: Individual,
+    {
+        Some(PreparedSelection::weighted(population, population.iter().map(Individual::fitness)))
+    }
+
+    fn probabilities<I>(&self, population: &[I]) -> Vec<f32>
+    where
+        I: Individual,
+    {
+        weight_shares(population.iter().map(Individual::fitness).collect())
+    }
+
+    fn describe(&self) -> MethodDescription {
+        MethodDescription::new("RouletteWheelSelection")
+    }
+}
+
+/// Min-max scales the population's fitness into `[0, 1]`, so that the worst
+/// individual gets `0.0` and the best one gets `1.0`; if everyone's fitness is
+/// the same, everyone gets `1.0`.
+pub fn normalize_fitness<I: Individual>(population: &[I]) -> Vec<f32> {
+    let min = population.iter().map(Individual::fitness).fold(f32::INFINITY, f32::min);
+    let max = population.iter().map(Individual::fitness).fold(f32::NEG_INFINITY, f32::max);
+    let range = max - min;
+
+    population
+        .iter()
+        .map(|individual| {
+            if range > 0.0 {
+                (individual.fitness() - min) / range
+            } else {
+                1.0
+            }
+        })
+        .collect()
+}
+
+/// Like [`RouletteWheelSelection`], but weighs individuals by their
+/// [normalized fitness](normalize_fitness), which makes it insensitive to
+/// the fitness' scale and offset.
+#[derive(Clone, Debug, Default)]
+pub struct MinMaxScaledSelection;
+
+impl MinMaxScaledSelection {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SelectionMethod for MinMaxScaledSelection {
+    fn select<'a, I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I]
+    ) -> &'a I
+    where
+        I: Individual,
+    {
+        self.prepare(population)
+            .expect("min-max scaled selection can always be prepared")
+            .select(rng)
+    }
+
+    fn prepare<'a, I>(&self, population: &'a [I]) -> Option<PreparedSelection<'a, I>>
+    where
+        I: Individual,
+    {
+        Some(PreparedSelection::weighted(population, normalize_fitness(population)))
+    }
+
+    fn probabilities<I>(&self, population: &[I]) -> Vec<f32>
+    where
+        I: Individual,
+    {
+        weight_shares(normalize_fitness(population))
+    }
+
+    fn describe(&self) -> MethodDescription {
+        MethodDescription::new("MinMaxScaledSelection")
     }
 }
 
@@ -135,6 +450,12 @@ impl Individual for TestIndividual {
             Self::WithFitness { fitness } => *fitness
         }
     }
+    fn chromosome_len(&self) -> Option<usize> {
+        match self {
+            Self::WithChromosome { chromosome } => Some(chromosome.len()),
+            Self::WithFitness { .. } => None
+        }
+    }
 }
 
 #[cfg(test)]
@@ -180,6 +501,555 @@ mod tests {
 }
 
 
+#[cfg(test)]
+mod probabilities {
+    use super::*;
+
+    fn population() -> Vec<TestIndividual> {
+        vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ]
+    }
+
+    #[test]
+    fn roulette_matches_fitness_share() {
+        let actual = RouletteWheelSelection::new().probabilities(&population());
+
+        approx::assert_relative_eq!(actual.as_slice(), [0.2, 0.1, 0.4, 0.3].as_slice());
+    }
+
+    #[test]
+    fn roulette_is_uniform_for_all_zero_fitness() {
+        let population: Vec<_> = (0..4).map(|_| TestIndividual::new(0.0)).collect();
+        let actual = RouletteWheelSelection::new().probabilities(&population);
+
+        approx::assert_relative_eq!(actual.as_slice(), [0.25; 4].as_slice());
+    }
+
+    #[test]
+    fn min_max_scaled_matches_normalized_fitness_share() {
+        let actual = MinMaxScaledSelection::new().probabilities(&population());
+
+        approx::assert_relative_eq!(actual.as_slice(), [1.0 / 6.0, 0.0, 0.5, 1.0 / 3.0].as_slice());
+    }
+
+    #[test]
+    fn sum_up_to_one() {
+        use rand::seq::SliceRandom;
+
+        struct UniformSelection;
+
+        impl SelectionMethod for UniformSelection {
+            fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+            where
+                I: Individual,
+            {
+                population.choose(rng).expect("got an empty population")
+            }
+        }
+
+        let population = population();
+
+        for probabilities in [
+            RouletteWheelSelection::new().probabilities(&population),
+            MinMaxScaledSelection::new().probabilities(&population),
+            UniformSelection.probabilities(&population),
+        ] {
+            approx::assert_relative_eq!(probabilities.iter().sum::<f32>(), 1.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod all_zero_fitness {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn roulette_falls_back_to_uniform_selection() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population: Vec<_> = (0..4).map(|_| TestIndividual::new(0.0)).collect();
+
+        let mut hits = [0; 4];
+
+        for _ in 0..1000 {
+            let selected = RouletteWheelSelection::new().select(&mut rng, &population);
+            let id = population.iter().position(|i| std::ptr::eq(i, selected)).unwrap();
+
+            hits[id] += 1;
+        }
+
+        assert!(hits.iter().all(|&hits| hits > 200), "{:?}", hits);
+    }
+}
+
+#[cfg(test)]
+mod equal_fitness {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn histogram(method: &impl SelectionMethod) -> [usize; 5] {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let population: Vec<_> = (0..5).map(|_| TestIndividual::new(1.0)).collect();
+        let mut hits = [0; 5];
+
+        for _ in 0..1000 {
+            let selected = method.select(&mut rng, &population);
+            let id = population.iter().position(|i| std::ptr::eq(i, selected)).unwrap();
+
+            hits[id] += 1;
+        }
+
+        hits
+    }
+
+    fn assert_picked_equally_often(hits: [usize; 5]) {
+        for count in hits {
+            assert!((150..=250).contains(&count), "{:?}", hits);
+        }
+    }
+
+    #[test]
+    fn roulette_wheel_picks_tied_individuals_equally_often() {
+        assert_picked_equally_often(histogram(&RouletteWheelSelection::new()));
+    }
+
+    #[test]
+    fn min_max_scaled_picks_tied_individuals_equally_often() {
+        assert_picked_equally_often(histogram(&MinMaxScaledSelection::new()));
+    }
+}
+
+#[cfg(test)]
+mod min_max_scaled {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn population(offset: f32) -> Vec<TestIndividual> {
+        [2.0, 1.0, 4.0, 3.0]
+            .iter()
+            .map(|fitness| TestIndividual::new(fitness + offset))
+            .collect()
+    }
+
+    fn histogram(population: &[TestIndividual]) -> [usize; 4] {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut hits = [0; 4];
+
+        for _ in 0..1000 {
+            let selected = MinMaxScaledSelection::new().select(&mut rng, population);
+            let id = population.iter().position(|i| std::ptr::eq(i, selected)).unwrap();
+
+            hits[id] += 1;
+        }
+
+        hits
+    }
+
+    #[test]
+    fn normalizes_into_unit_range() {
+        assert_eq!(normalize_fitness(&population(0.0)), vec![1.0 / 3.0, 0.0, 1.0, 2.0 / 3.0]);
+    }
+
+    #[test]
+    fn gives_uniform_weights_to_equal_fitness() {
+        let population: Vec<_> = (0..3).map(|_| TestIndividual::new(5.0)).collect();
+
+        assert_eq!(normalize_fitness(&population), vec![1.0; 3]);
+    }
+
+    #[test]
+    fn ignores_fitness_offset() {
+        let hits = histogram(&population(0.0));
+
+        assert_eq!(hits, histogram(&population(100.0)));
+        assert_eq!(hits[1], 0);
+    }
+}
+
+#[cfg(test)]
+mod run_seeded {
+    use super::*;
+
+    fn run(seed: u64) -> Vec<Vec<u32>> {
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let population: Vec<_> = (0..10)
+            .map(|id| TestIndividual::create([id as f32, 1.0, 2.0].into_iter().collect()))
+            .collect();
+
+        ga.run_seeded(seed, &population, 20)
+            .iter()
+            .map(|individual| individual.chromosome().iter().map(|gene| gene.to_bits()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_yields_identical_populations() {
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42), run(43));
+    }
+}
+
+#[cfg(test)]
+mod run_with_callback {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn reports_each_generation_with_its_timings() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let population: Vec<_> = (0..50)
+            .map(|id| TestIndividual::create([id as f32, 1.0, 2.0].into_iter().collect()))
+            .collect();
+
+        let mut generations = Vec::new();
+
+        let evolved = ga.run_with_callback(&mut rng, &population, 5, |generation, population, timings| {
+            assert_eq!(population.len(), 50);
+            assert_eq!(timings.total() > Duration::ZERO, cfg!(feature = "std"));
+
+            generations.push(generation);
+        });
+
+        assert_eq!(evolved.len(), 50);
+        assert_eq!(generations, vec![1, 2, 3, 4, 5]);
+    }
+}
+
+#[cfg(test)]
+mod empty_population {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn evolves_into_empty_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_elitism(2);
+
+        assert!(ga.evolve::<TestIndividual>(&mut rng, &[]).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod elitism {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().cloned().collect())
+    }
+
+    #[test]
+    fn carries_the_fittest_over_unchanged() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(1.0, 0.5),
+        )
+        .with_elitism(2);
+
+        let population = vec![
+            individual(&[1.0, 1.0]),
+            individual(&[4.0, 4.0]),
+            individual(&[0.0, 0.0]),
+            individual(&[3.0, 2.0]),
+        ];
+
+        let evolved = ga.evolve(&mut rng, &population);
+
+        assert_eq!(evolved.len(), population.len());
+        assert_eq!(evolved[0], individual(&[4.0, 4.0]));
+        assert_eq!(evolved[1], individual(&[3.0, 2.0]));
+    }
+
+    #[test]
+    fn is_capped_at_population_size() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(1.0, 0.5),
+        )
+        .with_elitism(10);
+
+        let population = vec![individual(&[1.0]), individual(&[2.0])];
+
+        assert_eq!(ga.evolve(&mut rng, &population), vec![individual(&[2.0]), individual(&[1.0])]);
+    }
+
+    #[test]
+    fn breaks_ties_by_population_order() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(1.0, 0.5),
+        )
+        .with_elitism(2);
+
+        let population = vec![
+            individual(&[0.0, 1.0]),
+            individual(&[2.0, 1.0]),
+            individual(&[1.0, 2.0]),
+            individual(&[3.0, 0.0]),
+        ];
+
+        let evolved = ga.evolve(&mut rng, &population);
+
+        assert_eq!(evolved[0], individual(&[2.0, 1.0]));
+        assert_eq!(evolved[1], individual(&[1.0, 2.0]));
+    }
+
+    #[test]
+    fn never_loses_the_best_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            MinMaxScaledSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(1.0, 0.5),
+        )
+        .with_elitism(1);
+
+        let mut population: Vec<_> = (0..10)
+            .map(|id| individual(&[id as f32 / 10.0, 0.0, -(id as f32) / 10.0]))
+            .collect();
+
+        let best = |population: &[TestIndividual]| {
+            population.iter().map(Individual::fitness).fold(f32::NEG_INFINITY, f32::max)
+        };
+
+        let mut best_fitness = best(&population);
+
+        for _ in 0..20 {
+            population = ga.evolve(&mut rng, &population);
+
+            assert!(best(&population) >= best_fitness);
+            best_fitness = best(&population);
+        }
+    }
+}
+
+#[cfg(test)]
+mod crossover_rate {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().cloned().collect())
+    }
+
+    fn population() -> Vec<TestIndividual> {
+        (0..4).map(|i| individual(&[i as f32, 10.0 + i as f32, -(i as f32)])).collect()
+    }
+
+    fn ga(crossover_rate: f32) -> GeneticAlgorithm<RouletteWheelSelection> {
+        GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_crossover_rate(crossover_rate)
+    }
+
+    #[test]
+    fn zero_rate_copies_the_first_parent() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        // Replays `evolve()`'s draws to know which parent came first
+        let mut replay = rng.clone();
+        let selection = RouletteWheelSelection::new().prepare(&population).unwrap();
+        let mutation = GaussianMutation::new(0.0, 0.0);
+
+        for child in ga(0.0).evolve(&mut rng, &population) {
+            let parent_a = selection.select(&mut replay);
+            let parent_b = selection.select(&mut replay);
+
+            assert!(!replay.gen_bool(0.0));
+            mutation.mutate(&mut replay, &mut parent_a.chromosome().clone());
+
+            assert_eq!(child, *parent_a, "second parent was {:?}", parent_b);
+        }
+    }
+
+    #[test]
+    fn full_rate_crosses_over() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        let children = ga(1.0).evolve(&mut rng, &population);
+
+        assert!(children.iter().any(|child| !population.contains(child)));
+    }
+
+    #[test]
+    fn is_described() {
+        let crossover = ga(0.5).describe().crossover;
+        assert_eq!(crossover, MethodDescription::new("UniformCrossover").with("rate", 0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "crossover rate must be within [0, 1]")]
+    fn rejects_rate_outside_of_unit_range() {
+        ga(1.5);
+    }
+}
+
+#[cfg(test)]
+mod gene_bounds {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn keep_genes_within_bounds() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            MinMaxScaledSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(1.0, 10.0),
+        )
+        .with_gene_bounds(-0.5..=0.5);
+
+        let mut population: Vec<_> = (0..10)
+            .map(|_| TestIndividual::create(vec![0.0; 5].into_iter().collect()))
+            .collect();
+
+        for _ in 0..10 {
+            population = ga.evolve(&mut rng, &population);
+
+            for individual in &population {
+                assert!(individual.chromosome().iter().all(|gene| (-0.5..=0.5).contains(gene)));
+            }
+        }
+
+        // ... and the mutation did push them against the bounds
+        assert!(population
+            .iter()
+            .flat_map(|individual| individual.chromosome().iter())
+            .any(|gene| gene.abs() == 0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "gene bounds must not be empty")]
+    fn rejects_empty_bounds() {
+        GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_gene_bounds(1.0..=-1.0);
+    }
+}
+
+#[cfg(test)]
+mod thread_safety {
+    use super::*;
+
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn built_in_configuration_is_send_and_sync() {
+        assert_send::<GeneticAlgorithm<RouletteWheelSelection>>();
+        assert_sync::<GeneticAlgorithm<RouletteWheelSelection>>();
+        assert_send::<GeneticAlgorithm<MinMaxScaledSelection>>();
+        assert_sync::<GeneticAlgorithm<MinMaxScaledSelection>>();
+    }
+}
+
+#[cfg(test)]
+mod describe {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.25),
+        );
+
+        let expected = GaDescription {
+            selection: MethodDescription::new("RouletteWheelSelection"),
+            crossover: MethodDescription::new("UniformCrossover"),
+            mutation: MethodDescription::new("GaussianMutation")
+                .with("chance", 0.5)
+                .with("coeff", 0.25),
+        };
+
+        assert_eq!(ga.describe(), expected);
+    }
+
+    #[test]
+    fn falls_back_to_type_name() {
+        struct CustomCrossover;
+
+        impl CrossoverMethod for CustomCrossover {
+            fn crossover(
+                &self,
+                _: &mut dyn RngCore,
+                parent_a: &Chromosome,
+                _: &Chromosome
+            ) -> Chromosome {
+                parent_a.clone()
+            }
+        }
+
+        assert_eq!(CustomCrossover.describe(), MethodDescription::new("CustomCrossover"));
+    }
+}
+
+#[cfg(test)]
+mod chromosome_len {
+    use super::*;
+
+    #[test]
+    fn fitness_only_individual_has_none() {
+        assert_eq!(TestIndividual::new(1.0).chromosome_len(), None);
+    }
+
+    #[test]
+    fn chromosome_bearing_individual_has_its_length() {
+        let individual = TestIndividual::create(vec![1.0, 2.0, 3.0].into_iter().collect());
+
+        assert_eq!(individual.chromosome_len(), Some(3));
+    }
+}
+
 #[cfg(test)]
 mod population_expected {
     use super::*;
@@ -215,10 +1085,10 @@ mod population_expected {
         }
 
         let expected_population = vec![
-            individual(&[0.44769490, 2.0648358, 4.3058133]),
-            individual(&[1.21268670, 1.5538777, 2.8869110]),
-            individual(&[1.06176780, 2.2657390, 4.4287640]),
-            individual(&[0.95909685, 2.4618788, 4.0247330]),
+            individual(&[0.4476949, 2.0648358, 4.3058133]),
+            individual(&[1.2126867, 1.5538777, 2.886911]),
+            individual(&[1.0617678, 2.265739, 4.428764]),
+            individual(&[0.95909685, 2.4618788, 4.024733]),
         ];
 
         assert_eq!(population, expected_population);
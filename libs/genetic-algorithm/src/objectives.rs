@@ -0,0 +1,100 @@
+/// Combines several objectives into a single fitness, weighing each one by
+/// the weight at the same index - e.g. for an [`crate::Individual::fitness()`]
+/// that balances eating against exploring.
+///
+/// Panics if `objectives` and `weights` have different lengths.
+pub fn weighted_sum(objectives: &[f32], weights: &[f32]) -> f32 {
+    assert_eq!(objectives.len(), weights.len());
+
+    objectives
+        .iter()
+        .zip(weights)
+        .map(|(objective, weight)| objective * weight)
+        .sum()
+}
+
+/// Returns the indices (in ascending order) of individuals that aren't
+/// dominated by anyone else, assuming every objective is maximized.
+///
+/// One individual dominates another if it's at least as good in every
+/// objective and strictly better in at least one; so individuals with
+/// identical objectives don't dominate each other and are either all part of
+/// the front or all left out.
+///
+/// Panics if the objective vectors have different lengths.
+pub fn pareto_front(objectives: &[Vec<f32>]) -> Vec<usize> {
+    if let Some(first) = objectives.first() {
+        for individual in objectives {
+            assert_eq!(individual.len(), first.len());
+        }
+    }
+
+    (0..objectives.len())
+        .filter(|&candidate| {
+            !objectives
+                .iter()
+                .any(|other| dominates(other, &objectives[candidate]))
+        })
+        .collect()
+}
+
+fn dominates(a: &[f32], b: &[f32]) -> bool {
+    let mut strictly_better = false;
+
+    for (a, b) in a.iter().zip(b) {
+        if a < b {
+            return false;
+        }
+
+        if a > b {
+            strictly_better = true;
+        }
+    }
+
+    strictly_better
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod weighted_sum {
+        use super::*;
+
+        #[test]
+        fn test() {
+            approx::assert_relative_eq!(weighted_sum(&[2.0, 3.0, -1.0], &[0.5, 2.0, 4.0]), 3.0);
+            approx::assert_relative_eq!(weighted_sum(&[], &[]), 0.0);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_mismatched_lengths() {
+            weighted_sum(&[1.0, 2.0], &[1.0]);
+        }
+    }
+
+    mod pareto_front {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let objectives = vec![
+                vec![1.0, 5.0], // front
+                vec![2.0, 4.0], // front
+                vec![1.0, 4.0], // dominated by #0 and #1
+                vec![4.0, 1.0], // front
+                vec![3.0, 1.0], // dominated by #3
+                vec![2.0, 4.0], // duplicate of #1, front
+                vec![0.0, 0.0], // dominated by everyone
+            ];
+
+            assert_eq!(pareto_front(&objectives), vec![0, 1, 3, 5]);
+        }
+
+        #[test]
+        fn empty_population_has_empty_front() {
+            assert!(pareto_front(&[]).is_empty());
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use rand::distributions::{Distribution, WeightedError, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+/// Selection over a fixed population whose cumulative weights are computed
+/// once, so that each draw only costs a binary search instead of a pass over
+/// the whole population; see [`SelectionMethod::prepare()`].
+///
+/// [`SelectionMethod::prepare()`]: crate::SelectionMethod::prepare
+pub struct PreparedSelection<'a, I> {
+    population: &'a [I],
+    /// `None` if all the weights are zero, in which case everyone's picked
+    /// with the same probability.
+    weights: Option<WeightedIndex<f32>>,
+}
+
+impl<'a, I> PreparedSelection<'a, I> {
+    /// Prepares picking individuals proportionally to given weights, one per
+    /// individual; if all the weights are zero, picks uniformly instead.
+    ///
+    /// Panics if the population is empty or any weight is negative or NaN.
+    pub fn weighted(population: &'a [I], weights: impl IntoIterator<Item = f32>) -> Self {
+        let weights = match WeightedIndex::new(weights) {
+            Ok(weights) => Some(weights),
+            Err(WeightedError::AllWeightsZero) => None,
+            Err(WeightedError::NoItem) => panic!("got an empty population"),
+            Err(err) => panic!("got invalid weights: {}", err),
+        };
+
+        Self { population, weights }
+    }
+
+    pub fn select(&self, rng: &mut dyn RngCore) -> &'a I {
+        match &self.weights {
+            Some(weights) => &self.population[weights.sample(rng)],
+            None => self.population.choose(rng).expect("got an empty population"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::cell::Cell;
+
+    thread_local! {
+        /// How many times anyone's fitness has been evaluated.
+        static EVALUATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct CountingIndividual {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for CountingIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn fitness(&self) -> f32 {
+            EVALUATIONS.with(|evaluations| evaluations.set(evaluations.get() + 1));
+            self.chromosome.iter().sum()
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+    }
+
+    /// Roulette without preparation, i.e. the naive path.
+    struct NaiveRoulette;
+
+    impl SelectionMethod for NaiveRoulette {
+        fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+        where
+            I: Individual,
+        {
+            RouletteWheelSelection::new().select(rng, population)
+        }
+    }
+
+    fn population() -> Vec<CountingIndividual> {
+        (0..20)
+            .map(|id| CountingIndividual::create([id as f32, 1.0].into_iter().collect()))
+            .collect()
+    }
+
+    fn evolve<S: SelectionMethod>(selection_method: S) -> (Vec<CountingIndividual>, usize) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        let ga = GeneticAlgorithm::new(
+            selection_method,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        EVALUATIONS.with(|evaluations| evaluations.set(0));
+        let evolved = ga.evolve(&mut rng, &population);
+
+        (evolved, EVALUATIONS.with(|evaluations| evaluations.get()))
+    }
+
+    #[test]
+    fn matches_the_naive_path() {
+        let (prepared, _) = evolve(RouletteWheelSelection::new());
+        let (naive, _) = evolve(NaiveRoulette);
+
+        assert_eq!(prepared, naive);
+    }
+
+    #[test]
+    fn evaluates_fitness_once_per_evolve() {
+        let (_, prepared) = evolve(RouletteWheelSelection::new());
+        let (_, naive) = evolve(NaiveRoulette);
+
+        assert_eq!(prepared, 20);
+        assert_eq!(naive, 2 * 20 * 20);
+    }
+
+    #[test]
+    fn falls_back_to_uniform_for_all_zero_weights() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = [1, 2, 3];
+        let prepared = PreparedSelection::weighted(&population, [0.0; 3]);
+
+        let mut hits = [0; 3];
+
+        for _ in 0..300 {
+            hits[*prepared.select(&mut rng) - 1] += 1;
+        }
+
+        assert!(hits.iter().all(|&hits| hits > 70), "{:?}", hits);
+    }
+}
@@ -0,0 +1,506 @@
+use crate::*;
+
+/// Summary of a single gene's values across a population.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeneStat {
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    /// Population variance, i.e. divided by the number of individuals.
+    pub variance: f32,
+}
+
+/// Computes statistics of each gene across the population, indexed the same
+/// way as the chromosomes.
+///
+/// Panics if the chromosomes have different lengths.
+pub fn gene_statistics<I: Individual>(population: &[I]) -> Vec<GeneStat> {
+    let Some(first) = population.first() else {
+        return Vec::new();
+    };
+
+    let len = first.chromosome().len();
+
+    for individual in population {
+        assert_eq!(individual.chromosome().len(), len);
+    }
+
+    let n = population.len() as f32;
+
+    (0..len)
+        .map(|gene| {
+            let values = population
+                .iter()
+                .map(|individual| individual.chromosome()[gene]);
+
+            let min = values.clone().fold(f32::INFINITY, f32::min);
+            let max = values.clone().fold(f32::NEG_INFINITY, f32::max);
+            let mean = values.clone().sum::<f32>() / n;
+
+            let variance = values
+                .map(|value| (value - mean).powi(2))
+                .sum::<f32>() / n;
+
+            GeneStat { min, max, mean, variance }
+        })
+        .collect()
+}
+
+/// Returns everyone's fitness in ascending order.
+///
+/// Sorts using [`f32::total_cmp()`], so a NaN fitness doesn't make the order
+/// depend on the population's order - (positive) NaNs always come last.
+pub fn sorted_fitness<I: Individual>(population: &[I]) -> Vec<f32> {
+    let mut fitness: Vec<_> = population.iter().map(Individual::fitness).collect();
+    fitness.sort_by(f32::total_cmp);
+    fitness
+}
+
+/// Returns the `k` fittest individuals, fittest first; returns everyone if
+/// there are fewer than `k` individuals. Fitness is compared with
+/// [`f32::total_cmp()`], and equally fit individuals are ordered (and
+/// picked) by their position in the population, earlier first.
+///
+/// Only the top `k` get sorted, which makes it cheaper than sorting the
+/// whole population.
+pub fn top_k<I: Individual>(population: &[I], k: usize) -> Vec<&I> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let by_fitness_desc = |(a_id, a): &(usize, &I), (b_id, b): &(usize, &I)| {
+        b.fitness().total_cmp(&a.fitness()).then(a_id.cmp(b_id))
+    };
+
+    let mut top: Vec<_> = population.iter().enumerate().collect();
+
+    if k < top.len() {
+        top.select_nth_unstable_by(k - 1, by_fitness_desc);
+        top.truncate(k);
+    }
+
+    top.sort_unstable_by(by_fitness_desc);
+    top.into_iter().map(|(_, individual)| individual).collect()
+}
+
+/// Pools two populations and keeps the `keep` fittest individuals, fittest
+/// first - e.g. to combine the results of parallel runs; returns everyone if
+/// there are fewer than `keep` of them. Equally fit individuals keep their
+/// order, with `a`'s coming before `b`'s.
+pub fn merge_keep_best<I: Individual>(a: Vec<I>, b: Vec<I>, keep: usize) -> Vec<I> {
+    let mut merged = a;
+    merged.extend(b);
+
+    merged.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+    merged.truncate(keep);
+    merged
+}
+
+/// Returns the mean Euclidean distance between the chromosomes of `prev[i]`
+/// and `next[i]`, i.e. how far the population has moved between two
+/// generations; an empty population hasn't moved at all.
+///
+/// Panics if the populations, or any two matched chromosomes, have
+/// different sizes.
+pub fn mean_genotype_shift<I: Individual>(prev: &[I], next: &[I]) -> f32 {
+    assert_eq!(prev.len(), next.len());
+
+    if prev.is_empty() {
+        return 0.0;
+    }
+
+    let total: f32 = prev
+        .iter()
+        .zip(next)
+        .map(|(prev, next)| {
+            let (prev, next) = (prev.chromosome(), next.chromosome());
+
+            assert_eq!(prev.len(), next.len());
+
+            prev.iter()
+                .zip(next.iter())
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f32>()
+                .sqrt()
+        })
+        .sum();
+
+    total / prev.len() as f32
+}
+
+/// Returns whether every gene's spread (`max - min`) across the population
+/// is below `gene_tolerance`, i.e. whether everyone's nearly identical.
+///
+/// Panics if the chromosomes have different lengths.
+pub fn has_converged<I: Individual>(population: &[I], gene_tolerance: f32) -> bool {
+    gene_statistics(population)
+        .iter()
+        .all(|stat| stat.max - stat.min < gene_tolerance)
+}
+
+/// Returns the Pearson correlation between each gene's value and the
+/// individuals' fitness, indexed the same way as the chromosomes; genes
+/// which don't vary across the population (or a population whose fitness
+/// doesn't vary) correlate with nothing, so they get zero.
+///
+/// Panics if the chromosomes have different lengths.
+pub fn gene_fitness_correlation<I: Individual>(population: &[I]) -> Vec<f32> {
+    let stats = gene_statistics(population);
+
+    if population.is_empty() {
+        return Vec::new();
+    }
+
+    let n = population.len() as f32;
+    let fitness: Vec<_> = population.iter().map(Individual::fitness).collect();
+    let fitness_mean = fitness.iter().sum::<f32>() / n;
+    let fitness_variance = fitness
+        .iter()
+        .map(|f| (f - fitness_mean).powi(2))
+        .sum::<f32>() / n;
+
+    stats
+        .iter()
+        .enumerate()
+        .map(|(gene, stat)| {
+            if stat.variance == 0.0 || fitness_variance == 0.0 {
+                return 0.0;
+            }
+
+            let covariance = population
+                .iter()
+                .zip(&fitness)
+                .map(|(individual, f)| {
+                    (individual.chromosome()[gene] - stat.mean) * (f - fitness_mean)
+                })
+                .sum::<f32>() / n;
+
+            covariance / (stat.variance * fitness_variance).sqrt()
+        })
+        .collect()
+}
+
+/// Returns the least-squares slope of the last `window` values of `history`
+/// (e.g. the best fitness of each generation), i.e. how much they've been
+/// improving per generation lately; zero if there are fewer than `window`
+/// values, or if `window` is less than two.
+pub fn improvement_rate(history: &[f32], window: usize) -> f32 {
+    if window < 2 || history.len() < window {
+        return 0.0;
+    }
+
+    let recent = &history[history.len() - window..];
+    let n = window as f32;
+    let x_mean = (n - 1.0) / 2.0;
+    let y_mean = recent.iter().sum::<f32>() / n;
+
+    let (covariance, x_variance) = recent
+        .iter()
+        .enumerate()
+        .fold((0.0, 0.0), |(covariance, x_variance), (x, y)| {
+            let dx = x as f32 - x_mean;
+            (covariance + dx * (y - y_mean), x_variance + dx * dx)
+        });
+
+    covariance / x_variance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().cloned().collect())
+    }
+
+    mod gene_statistics {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let population = vec![
+                individual(&[1.0, 0.0, 5.0]),
+                individual(&[2.0, 0.0, -1.0]),
+                individual(&[3.0, 0.0, 2.0]),
+            ];
+
+            let actual = gene_statistics(&population);
+
+            assert_eq!(actual.len(), 3);
+
+            assert_eq!(actual[0].min, 1.0);
+            assert_eq!(actual[0].max, 3.0);
+            approx::assert_relative_eq!(actual[0].mean, 2.0);
+            approx::assert_relative_eq!(actual[0].variance, 2.0 / 3.0);
+
+            approx::assert_relative_eq!(actual[1].mean, 0.0);
+            approx::assert_relative_eq!(actual[1].variance, 0.0);
+
+            assert_eq!(actual[2].min, -1.0);
+            assert_eq!(actual[2].max, 5.0);
+            approx::assert_relative_eq!(actual[2].mean, 2.0);
+            approx::assert_relative_eq!(actual[2].variance, 6.0);
+        }
+
+        #[test]
+        fn empty_population_has_no_statistics() {
+            assert!(gene_statistics::<TestIndividual>(&[]).is_empty());
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_chromosomes_of_different_lengths() {
+            gene_statistics(&[individual(&[1.0]), individual(&[1.0, 2.0])]);
+        }
+    }
+
+    mod gene_fitness_correlation {
+        use super::*;
+
+        #[test]
+        fn gene_that_predicts_fitness_correlates_fully() {
+            // Fitness is the sum of the genes; the second gene cancels out
+            // the third one, so the first gene alone determines the fitness
+            let population = vec![
+                individual(&[1.0, 0.5, -0.5, 2.0]),
+                individual(&[2.0, -1.0, 1.0, 2.0]),
+                individual(&[3.0, 0.0, 0.0, 2.0]),
+                individual(&[4.0, 2.0, -2.0, 2.0]),
+            ];
+
+            let actual = gene_fitness_correlation(&population);
+
+            assert_eq!(actual.len(), 4);
+            approx::assert_relative_eq!(actual[0], 1.0, epsilon = 1e-5);
+            assert!(actual[1].abs() < 1.0);
+            assert!(actual[2].abs() < 1.0);
+            assert_eq!(actual[3], 0.0);
+        }
+
+        #[test]
+        fn anti_correlated_gene() {
+            let population: Vec<_> = (0..5)
+                .map(|i| {
+                    TestIndividual::create(vec![i as f32, -2.0 * i as f32].into_iter().collect())
+                })
+                .collect();
+
+            // Fitness is `-i`, so the first gene is anti-correlated
+            let actual = gene_fitness_correlation(&population);
+
+            approx::assert_relative_eq!(actual[0], -1.0, epsilon = 1e-5);
+            approx::assert_relative_eq!(actual[1], 1.0, epsilon = 1e-5);
+        }
+
+        #[test]
+        fn empty_population_has_no_correlations() {
+            assert!(gene_fitness_correlation::<TestIndividual>(&[]).is_empty());
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_chromosomes_of_different_lengths() {
+            gene_fitness_correlation(&[individual(&[1.0]), individual(&[1.0, 2.0])]);
+        }
+    }
+
+    mod has_converged {
+        use super::*;
+
+        #[test]
+        fn clones_have_converged() {
+            let population: Vec<_> = (0..5).map(|_| individual(&[1.0, -2.0, 0.5])).collect();
+
+            assert!(has_converged(&population, 1e-3));
+        }
+
+        #[test]
+        fn diverse_population_has_not_converged() {
+            let population = vec![
+                individual(&[1.0, -2.0, 0.5]),
+                individual(&[1.0, -2.0, 0.6]),
+            ];
+
+            assert!(!has_converged(&population, 1e-3));
+            assert!(has_converged(&population, 0.2));
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_chromosomes_of_different_lengths() {
+            has_converged(&[individual(&[1.0]), individual(&[1.0, 2.0])], 1.0);
+        }
+    }
+
+    mod sorted_fitness {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let population = vec![
+                TestIndividual::new(2.0),
+                TestIndividual::new(f32::NAN),
+                TestIndividual::new(-1.0),
+                TestIndividual::new(1.0),
+            ];
+
+            let actual = sorted_fitness(&population);
+
+            assert_eq!(actual[..3], [-1.0, 1.0, 2.0]);
+            assert!(actual[3].is_nan());
+
+            let mut reversed = population.clone();
+            reversed.reverse();
+
+            // Compared bitwise, since NaN isn't equal to itself
+            assert_eq!(bits(&sorted_fitness(&reversed)), bits(&actual));
+        }
+
+        fn bits(fitness: &[f32]) -> Vec<u32> {
+            fitness.iter().map(|fitness| fitness.to_bits()).collect()
+        }
+    }
+
+    mod top_k {
+        use super::*;
+
+        fn population() -> Vec<TestIndividual> {
+            [5.0, 1.0, 9.0, 3.0, 7.0, 2.0, 8.0]
+                .into_iter()
+                .map(TestIndividual::new)
+                .collect()
+        }
+
+        fn fitness(individuals: Vec<&TestIndividual>) -> Vec<f32> {
+            individuals.into_iter().map(Individual::fitness).collect()
+        }
+
+        #[test]
+        fn matches_full_sort() {
+            let population = population();
+
+            let mut expected = sorted_fitness(&population);
+            expected.reverse();
+
+            for k in 0..=population.len() {
+                assert_eq!(fitness(top_k(&population, k)), expected[..k]);
+            }
+        }
+
+        #[test]
+        fn breaks_ties_by_population_order() {
+            let population = vec![
+                TestIndividual::new(1.0),
+                TestIndividual::new(2.0),
+                TestIndividual::new(1.0),
+                TestIndividual::new(2.0),
+                TestIndividual::new(2.0),
+            ];
+
+            for k in 1..=population.len() {
+                let top = top_k(&population, k);
+
+                let expected: Vec<_> = [1, 3, 4, 0, 2]
+                    .into_iter()
+                    .take(k)
+                    .map(|id| &population[id])
+                    .collect();
+
+                assert!(top.iter().zip(&expected).all(|(a, b)| std::ptr::eq(*a, *b)), "k = {}", k);
+            }
+        }
+
+        #[test]
+        fn returns_everyone_if_k_exceeds_population() {
+            assert_eq!(fitness(top_k(&population(), 100)), vec![9.0, 8.0, 7.0, 5.0, 3.0, 2.0, 1.0]);
+        }
+    }
+
+    mod merge_keep_best {
+        use super::*;
+
+        fn population(fitness: &[f32]) -> Vec<TestIndividual> {
+            fitness.iter().copied().map(TestIndividual::new).collect()
+        }
+
+        #[test]
+        fn keeps_the_globally_fittest() {
+            let merged = merge_keep_best(
+                population(&[4.0, 9.0, 1.0]),
+                population(&[7.0, 2.0, 8.0]),
+                4,
+            );
+
+            assert_eq!(merged, population(&[9.0, 8.0, 7.0, 4.0]));
+        }
+
+        #[test]
+        fn returns_everyone_if_keep_exceeds_population() {
+            let merged = merge_keep_best(population(&[1.0]), population(&[3.0, 2.0]), 100);
+
+            assert_eq!(merged, population(&[3.0, 2.0, 1.0]));
+        }
+    }
+
+    mod improvement_rate {
+        use super::*;
+
+        #[test]
+        fn matches_slope_of_linear_history() {
+            let history: Vec<_> = (0..20).map(|x| 3.0 + 0.5 * x as f32).collect();
+
+            approx::assert_relative_eq!(improvement_rate(&history, 10), 0.5, epsilon = 1e-5);
+            approx::assert_relative_eq!(improvement_rate(&history, 20), 0.5, epsilon = 1e-5);
+        }
+
+        #[test]
+        fn only_looks_at_the_window() {
+            // Flat for a while, then improving by 2.0 per generation
+            let history = [1.0, 1.0, 1.0, 1.0, 2.0, 4.0, 6.0];
+
+            approx::assert_relative_eq!(improvement_rate(&history, 3), 2.0, epsilon = 1e-5);
+        }
+
+        #[test]
+        fn is_zero_without_enough_points() {
+            assert_eq!(improvement_rate(&[1.0, 2.0], 3), 0.0);
+            assert_eq!(improvement_rate(&[1.0, 2.0], 1), 0.0);
+        }
+    }
+
+    mod mean_genotype_shift {
+        use super::*;
+
+        fn population() -> Vec<TestIndividual> {
+            vec![individual(&[1.0, 2.0]), individual(&[-3.0, 0.5])]
+        }
+
+        #[test]
+        fn unchanged_population_has_not_moved() {
+            assert_eq!(mean_genotype_shift(&population(), &population()), 0.0);
+        }
+
+        #[test]
+        fn uniform_shift() {
+            let shifted: Vec<_> = population()
+                .iter()
+                .map(|prev| {
+                    let genes: Vec<_> = prev.chromosome().iter().map(|gene| gene + 3.0).collect();
+                    individual(&genes)
+                })
+                .collect();
+
+            // Every gene moved by 3.0, so every chromosome moved by `3√2`
+            approx::assert_relative_eq!(
+                mean_genotype_shift(&population(), &shifted),
+                3.0 * 2.0f32.sqrt()
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_populations_of_different_sizes() {
+            mean_genotype_shift(&population(), &population()[1..]);
+        }
+    }
+}
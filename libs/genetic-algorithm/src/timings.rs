@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+/// Time spent in each of the genetic operators during one generation; see
+/// [`GeneticAlgorithm::run_with_callback()`].
+///
+/// Only measured with the `std` feature enabled - otherwise it's always
+/// zero, so that the measuring doesn't cost anything.
+///
+/// [`GeneticAlgorithm::run_with_callback()`]: crate::GeneticAlgorithm::run_with_callback
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub selection: Duration,
+    pub crossover: Duration,
+    pub mutation: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.selection + self.crossover + self.mutation
+    }
+}
+
+/// Runs `f`, adding the time it took to `elapsed`.
+#[cfg(feature = "std")]
+pub(crate) fn measure<T>(elapsed: &mut Duration, f: impl FnOnce() -> T) -> T {
+    let start = std::time::Instant::now();
+    let result = f();
+
+    *elapsed += start.elapsed();
+    result
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn measure<T>(_: &mut Duration, f: impl FnOnce() -> T) -> T {
+    f()
+}
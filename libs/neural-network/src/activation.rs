@@ -0,0 +1,37 @@
+/// Function applied to each neuron's weighted sum of inputs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Activation {
+    /// `max(0, x)`; the network's default.
+    #[default]
+    Relu,
+
+    /// `tanh(x)`, bounded to `(-1, 1)`.
+    Tanh,
+
+    /// `1 / (1 + e^-x)`, bounded to `(0, 1)`.
+    Sigmoid,
+}
+
+impl Activation {
+    pub fn apply(&self, x: f32) -> f32 {
+        match self {
+            Self::Relu => x.max(0.0),
+            Self::Tanh => x.tanh(),
+            Self::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        assert_eq!(Activation::Relu.apply(-2.0), 0.0);
+        assert_eq!(Activation::Relu.apply(2.0), 2.0);
+
+        approx::assert_relative_eq!(Activation::Tanh.apply(0.5), 0.5f32.tanh());
+        approx::assert_relative_eq!(Activation::Sigmoid.apply(0.0), 0.5);
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use crate::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TopologyError {
+    /// There were no networks to work with.
+    Empty,
+
+    /// Given network's layer sizes or (layer or neuron) activations differ
+    /// from the first network's.
+    Mismatch { network: usize },
+}
+
+impl Network {
+    /// Creates a network whose each bias and weight is the mean of the
+    /// corresponding ones across `networks`, which must all share the same
+    /// topology (including activations).
+    pub fn average(networks: &[&Network]) -> Result<Network, TopologyError> {
+        let (first, rest) = networks.split_first().ok_or(TopologyError::Empty)?;
+
+        for (id, network) in rest.iter().enumerate() {
+            let same_topology = network.layer_sizes() == first.layer_sizes()
+                && network
+                    .layers
+                    .iter()
+                    .zip(&first.layers)
+                    .all(|(a, b)| {
+                        a.activation == b.activation
+                            && a.neurons
+                                .iter()
+                                .zip(&b.neurons)
+                                .all(|(a, b)| a.activation == b.activation)
+                    });
+
+            if !same_topology {
+                return Err(TopologyError::Mismatch { network: id + 1 });
+            }
+        }
+
+        let mut sums = first.weights();
+
+        for network in rest {
+            for (sum, weight) in sums.iter_mut().zip(network.weights()) {
+                *sum += weight;
+            }
+        }
+
+        let n = networks.len() as f32;
+        let mut average = (*first).clone();
+
+        for (weight, sum) in average.weights_mut().zip(sums) {
+            *weight = sum / n;
+        }
+
+        Ok(average)
+    }
+}
+
+impl fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "got no networks"),
+
+            Self::Mismatch { network } => {
+                write!(f, "network #{} has a different topology than network #0", network)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TopologyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYERS: &[LayerTopology] = &[LayerTopology::new(2), LayerTopology::new(1)];
+
+    #[test]
+    fn takes_the_mean_of_each_weight() {
+        let a = Network::from_weights(LAYERS, vec![1.0, 2.0, -3.0]);
+        let b = Network::from_weights(LAYERS, vec![0.0, 4.0, 3.0]);
+
+        let actual = Network::average(&[&a, &b]).unwrap().weights();
+
+        approx::assert_relative_eq!(actual.as_slice(), [0.5, 3.0, 0.0].as_slice());
+    }
+
+    #[test]
+    fn rejects_mismatched_topologies() {
+        let a = Network::from_weights(LAYERS, vec![1.0, 2.0, -3.0]);
+        let b = Network::from_weights(&[LayerTopology::new(1), LayerTopology::new(1)], vec![0.0, 4.0]);
+        let c = Network::from_weights(
+            &[LayerTopology::new(2), LayerTopology::new(1).with_activation(Activation::Tanh)],
+            vec![1.0, 2.0, -3.0],
+        );
+
+        assert_eq!(Network::average(&[&a, &a, &b]).unwrap_err(), TopologyError::Mismatch { network: 2 });
+        assert_eq!(Network::average(&[&a, &c]).unwrap_err(), TopologyError::Mismatch { network: 1 });
+    }
+
+    #[test]
+    fn rejects_no_networks() {
+        assert_eq!(Network::average(&[]).unwrap_err(), TopologyError::Empty);
+    }
+}
@@ -0,0 +1,40 @@
+use genetic_algorithm as ga;
+
+use crate::*;
+
+/// Builds a network of given topology from a chromosome, whose genes are
+/// the network's weights in the order of [`Network::weights()`] - e.g. an
+/// evolved [`ga::Individual`]'s.
+pub fn network_from_chromosome(
+    layers: &[LayerTopology],
+    chromosome: &ga::Chromosome
+) -> Result<Network, WeightError> {
+    let weights = Weights::for_topology(layers, chromosome.iter().copied())?;
+
+    Ok(Network::from_validated_weights(layers, weights))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYERS: &[LayerTopology] = &[LayerTopology::new(2), LayerTopology::new(1)];
+
+    #[test]
+    fn builds_network_from_chromosome() {
+        let chromosome: ga::Chromosome = vec![0.1, 0.2, 0.3].into_iter().collect();
+        let network = network_from_chromosome(LAYERS, &chromosome).unwrap();
+
+        assert_eq!(network.weights(), vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn rejects_chromosome_of_wrong_length() {
+        let chromosome: ga::Chromosome = vec![0.1, 0.2].into_iter().collect();
+
+        assert_eq!(
+            network_from_chromosome(LAYERS, &chromosome).unwrap_err(),
+            WeightError::LengthMismatch { expected: 3, actual: 2 }
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::*;
+
+/// Counts how many distinct behaviors the networks exhibit: each network's
+/// outputs for all the `probes` get concatenated, and then the networks are
+/// greedily grouped - a network joins the first cluster whose founder's
+/// outputs lie within `threshold` (Euclidean distance) of its own, or
+/// founds a new cluster otherwise.
+///
+/// Panics if the networks' input or output sizes differ.
+pub fn behavioral_clusters(networks: &[&Network], probes: &[Vec<f32>], threshold: f32) -> usize {
+    let mut founders: Vec<Vec<f32>> = Vec::new();
+
+    for network in networks {
+        let outputs: Vec<f32> = network.propagate_batch(probes).into_iter().flatten().collect();
+
+        let clustered = founders.iter().any(|founder| {
+            assert_eq!(founder.len(), outputs.len(), "networks' outputs differ in size");
+
+            let distance = founder
+                .iter()
+                .zip(&outputs)
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f32>()
+                .sqrt();
+
+            distance <= threshold
+        });
+
+        if !clustered {
+            founders.push(outputs);
+        }
+    }
+
+    founders.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(weights: Vec<f32>) -> Network {
+        Network::from_weights(&[LayerTopology::new(2), LayerTopology::new(1)], weights)
+    }
+
+    fn probes() -> Vec<Vec<f32>> {
+        vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]]
+    }
+
+    #[test]
+    fn near_duplicates_share_a_cluster() {
+        let a = network(vec![0.0, 1.0, 0.0]);
+        let a_prime = network(vec![0.0, 1.01, 0.0]);
+        let b = network(vec![0.0, 0.0, 1.0]);
+
+        assert_eq!(behavioral_clusters(&[&a, &b, &a_prime], &probes(), 0.1), 2);
+    }
+
+    #[test]
+    fn zero_threshold_separates_everyone_but_clones() {
+        let a = network(vec![0.0, 1.0, 0.0]);
+        let a_prime = network(vec![0.0, 1.01, 0.0]);
+
+        assert_eq!(behavioral_clusters(&[&a, &a_prime, &a], &probes(), 0.0), 2);
+    }
+
+    #[test]
+    fn no_networks_form_no_clusters() {
+        assert_eq!(behavioral_clusters(&[], &probes(), 0.1), 0);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::*;
+
+/// Returns the `(layer, neuron)` pairs of neurons which output zero for
+/// every probe, in every network - i.e. neurons that never activate and
+/// could be pruned. Layers are indexed as in [`Network::layer_weights()`].
+///
+/// Panics if the networks' layer sizes differ.
+pub fn dead_neuron_report(networks: &[&Network], probes: &[Vec<f32>]) -> Vec<(usize, usize)> {
+    let Some(first) = networks.first() else {
+        return Vec::new();
+    };
+
+    let sizes = first.layer_sizes();
+    let mut alive: Vec<Vec<bool>> = sizes[1..].iter().map(|&size| vec![false; size]).collect();
+
+    for network in networks {
+        assert_eq!(network.layer_sizes(), sizes, "networks' topologies differ");
+
+        for probe in probes {
+            let mut inputs = probe.clone();
+
+            if let Some(normalization) = &network.normalization {
+                normalization.apply(&mut inputs);
+            }
+
+            for (layer, alive) in network.layers.iter().zip(&mut alive) {
+                inputs = layer.propagate(inputs);
+
+                for (alive, &output) in alive.iter_mut().zip(&inputs) {
+                    *alive |= output != 0.0;
+                }
+            }
+        }
+    }
+
+    alive
+        .iter()
+        .enumerate()
+        .flat_map(|(layer, alive)| {
+            alive
+                .iter()
+                .enumerate()
+                .filter(|(_, alive)| !**alive)
+                .map(move |(neuron, _)| (layer, neuron))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probes() -> Vec<Vec<f32>> {
+        vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.5, 0.5]]
+    }
+
+    #[test]
+    fn reports_neuron_with_negative_weights_and_bias() {
+        let network = Network::from_weights(
+            &[LayerTopology::new(2), LayerTopology::new(2), LayerTopology::new(1)],
+            vec![
+                // Layer 0: a live neuron and a dead one
+                0.0, 1.0, 1.0,
+                -0.5, -1.0, -2.0,
+                // Layer 1
+                0.1, 1.0, 1.0,
+            ],
+        );
+
+        assert_eq!(dead_neuron_report(&[&network], &probes()), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn neuron_alive_in_any_network_is_not_dead() {
+        let layers = [LayerTopology::new(2), LayerTopology::new(1)];
+
+        let dead = Network::from_weights(&layers, vec![-1.0, -1.0, -1.0]);
+        let alive = Network::from_weights(&layers, vec![0.0, 1.0, 0.0]);
+
+        assert_eq!(dead_neuron_report(&[&dead], &probes()), vec![(0, 0)]);
+        assert!(dead_neuron_report(&[&dead, &alive], &probes()).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "networks' topologies differ")]
+    fn rejects_different_topologies() {
+        let a = Network::from_weights(&[LayerTopology::new(2), LayerTopology::new(1)], vec![0.0; 3]);
+        let b = Network::from_weights(&[LayerTopology::new(1), LayerTopology::new(1)], vec![0.0; 2]);
+
+        dead_neuron_report(&[&a, &b], &probes());
+    }
+}
@@ -0,0 +1,73 @@
+use crate::*;
+
+/// Describes which part of a network a single gene (i.e. one of the values
+/// returned by [`Network::weights()`]) controls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GeneLocus {
+    /// Layer index, as in [`Network::layer_weights()`].
+    pub layer: usize,
+    pub neuron: usize,
+    pub kind: GeneKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeneKind {
+    Bias,
+    /// Weight of the connection from given input of the neuron.
+    Weight(usize),
+}
+
+/// Returns the locus of each gene of a network of given topology, ordered
+/// the same way as [`Network::weights()`].
+pub fn topology_layout(layers: &[LayerTopology]) -> Vec<GeneLocus> {
+    layers
+        .windows(2)
+        .enumerate()
+        .flat_map(|(layer, layers)| {
+            let inputs = layers[0].neurons;
+
+            (0..layers[1].neurons).flat_map(move |neuron| {
+                std::iter::once(GeneKind::Bias)
+                    .chain((0..inputs).map(GeneKind::Weight))
+                    .map(move |kind| GeneLocus { layer, neuron, kind })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYERS: &[LayerTopology] = &[
+        LayerTopology::new(3),
+        LayerTopology::new(2),
+        LayerTopology::new(1),
+    ];
+
+    #[test]
+    fn has_one_locus_per_gene() {
+        assert_eq!(topology_layout(LAYERS).len(), Network::weights_count(LAYERS));
+    }
+
+    #[test]
+    fn starts_each_neuron_with_its_bias() {
+        let layout = topology_layout(LAYERS);
+
+        for (gene, locus) in layout.iter().enumerate() {
+            let starts_neuron = gene == 0
+                || (layout[gene - 1].layer, layout[gene - 1].neuron) != (locus.layer, locus.neuron);
+
+            assert_eq!(starts_neuron, locus.kind == GeneKind::Bias, "gene {}", gene);
+        }
+    }
+
+    #[test]
+    fn follows_weights_order() {
+        let layout = topology_layout(LAYERS);
+
+        assert_eq!(layout[5], GeneLocus { layer: 0, neuron: 1, kind: GeneKind::Weight(0) });
+        assert_eq!(layout[8], GeneLocus { layer: 1, neuron: 0, kind: GeneKind::Bias });
+        assert_eq!(layout[10], GeneLocus { layer: 1, neuron: 0, kind: GeneKind::Weight(1) });
+    }
+}
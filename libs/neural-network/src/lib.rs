@@ -1,80 +1,560 @@
-
+use std::ops::RangeInclusive;
 
 use rand::prelude::*;
-use rand_chacha::ChaCha8Rng;
 
+mod activation;
+mod average;
+#[cfg(feature = "chromosome")]
+mod chromosome;
+mod clusters;
+mod dead_neurons;
+mod layout;
+mod weights;
+
+pub use self::{activation::*, average::*, clusters::*, dead_neurons::*, layout::*, weights::*};
+
+#[cfg(feature = "chromosome")]
+pub use self::chromosome::*;
+
+#[derive(Clone, Debug)]
 pub struct Network {
-    layers: Vec<Layer>
+    layers: Vec<Layer>,
+    normalization: Option<InputNormalization>
 }
 
+/// Per-input statistics each input is standardized with before propagating.
+#[derive(Clone, Debug)]
+struct InputNormalization {
+    means: Vec<f32>,
+    stds: Vec<f32>
+}
+
+#[derive(Clone, Debug)]
 struct Layer {
-    neurons: Vec<Neuron>
+    neurons: Vec<Neuron>,
+    activation: Activation
 }
+
+#[derive(Clone, Debug)]
 struct Neuron {
     bias: f32,
-    weights: Vec<f32>
+    weights: Vec<f32>,
+    /// Overrides the layer's activation for this neuron.
+    activation: Option<Activation>
 }
 
+#[derive(Clone, Copy, Debug)]
 pub struct LayerTopology {
     pub neurons: usize,
+    /// Activation of this layer's neurons, or `None` for the default one;
+    /// ignored for the input layer.
+    pub activation: Option<Activation>,
+}
+
+impl LayerTopology {
+    /// Creates a layer with the default activation.
+    pub const fn new(neurons: usize) -> Self {
+        Self { neurons, activation: None }
+    }
+
+    pub const fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = Some(activation);
+        self
+    }
 }
 
 impl Network {
 
-    pub fn random(layers: &[LayerTopology]) -> Self {
+    /// Panics if there are less than two layers, or any of them is empty.
+    pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology]) -> Self {
         assert!(layers.len() > 1);
+
+        if let Err(err) = check_layers(layers) {
+            panic!("{}", err);
+        }
         
         let layers = layers
                         .windows(2)
                         .map(|layers| {
-                            Layer::random(layers[0].neurons, layers[1].neurons)
+                            Layer::random(rng, layers[0].neurons, layers[1])
                         })
                         .collect();
-        Self { layers }
+        Self { layers, normalization: None }
+    }
+
+    /// Builds a network from a flat list of weights, as returned by
+    /// [`Network::weights()`].
+    ///
+    /// Panics if there are less than two layers, any of them is empty, or
+    /// the number of weights doesn't match; see [`Weights::for_topology()`]
+    /// for a fallible alternative.
+    pub fn from_weights(
+        layers: &[LayerTopology],
+        weights: impl IntoIterator<Item = f32>
+    ) -> Self {
+        assert!(layers.len() > 1);
+
+        if let Err(err) = check_layers(layers) {
+            panic!("{}", err);
+        }
+
+        let mut weights = weights.into_iter();
+
+        let layers = layers
+            .windows(2)
+            .map(|layers| {
+                Layer::from_weights(layers[0].neurons, layers[1], &mut weights)
+            })
+            .collect();
+
+        if weights.next().is_some() {
+            panic!("got too many weights");
+        }
+
+        Self { layers, normalization: None }
+    }
+
+    /// Builds a network from weights which have already been checked
+    /// against the topology; see [`Weights::for_topology()`].
+    pub fn from_validated_weights(layers: &[LayerTopology], weights: Weights) -> Self {
+        assert!(weights.matches(layers), "weights were validated for another topology");
+
+        Self::from_weights(layers, weights)
+    }
+
+    /// Builds a network from per-layer weight matrices and biases, as
+    /// returned by [`Network::weight_matrices()`] - e.g. trained elsewhere.
+    pub fn from_weight_matrices(
+        layers: &[LayerTopology],
+        matrices: Vec<(Vec<Vec<f32>>, Vec<f32>)>,
+    ) -> Result<Self, WeightError> {
+        assert!(layers.len() > 1);
+        check_layers(layers)?;
+
+        if matrices.len() != layers.len() - 1 {
+            return Err(WeightError::LayerCountMismatch {
+                expected: layers.len() - 1,
+                actual: matrices.len(),
+            });
+        }
+
+        let mut weights = Vec::with_capacity(Self::weights_count(layers));
+
+        for (layer, (sizes, (matrix, biases))) in layers.windows(2).zip(matrices).enumerate() {
+            let (rows, columns) = (sizes[1].neurons, sizes[0].neurons);
+
+            let fits = matrix.len() == rows
+                && biases.len() == rows
+                && matrix.iter().all(|row| row.len() == columns);
+
+            if !fits {
+                return Err(WeightError::ShapeMismatch { layer, rows, columns });
+            }
+
+            for (row, bias) in matrix.into_iter().zip(biases) {
+                weights.push(bias);
+                weights.extend(row);
+            }
+        }
+
+        Ok(Self::from_weights(layers, weights))
+    }
+
+    /// Makes `propagate()` standardize each input as `(x - mean) / std`
+    /// before feeding it to the first layer.
+    ///
+    /// Panics if there's not exactly one mean and one std per input, or if
+    /// any std isn't positive.
+    pub fn with_input_normalization(mut self, means: Vec<f32>, stds: Vec<f32>) -> Self {
+        let inputs = self.layer_sizes()[0];
+
+        assert_eq!(means.len(), inputs, "got {} means for {} inputs", means.len(), inputs);
+        assert_eq!(stds.len(), inputs, "got {} stds for {} inputs", stds.len(), inputs);
+        assert!(stds.iter().all(|&std| std > 0.0), "stds must be positive");
+
+        self.normalization = Some(InputNormalization { means, stds });
+        self
+    }
+
+    /// Returns how many biases and weights a network of given topology has.
+    pub fn weights_count(layers: &[LayerTopology]) -> usize {
+        layers
+            .windows(2)
+            .map(|layers| (layers[0].neurons + 1) * layers[1].neurons)
+            .sum()
+    }
+
+    /// Returns all the biases and weights, layer by layer and neuron by
+    /// neuron - each neuron contributes its bias followed by its weights.
+    pub fn weights(&self) -> Vec<f32> {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.weights())
+            .collect()
+    }
+
+    /// Iterates over the biases and weights in the order of
+    /// [`Network::weights()`].
+    pub(crate) fn weights_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+        self.layers
+            .iter_mut()
+            .flat_map(|layer| &mut layer.neurons)
+            .flat_map(|neuron| std::iter::once(&mut neuron.bias).chain(&mut neuron.weights))
+    }
+
+    /// Rounds each bias and weight to the nearest of `levels` evenly spaced
+    /// values spanning from the smallest to the largest one.
+    ///
+    /// Panics if `levels` is less than two.
+    pub fn quantize(&mut self, levels: u32) {
+        assert!(levels >= 2, "quantization needs at least two levels, got {}", levels);
+
+        let weights = self.weights();
+        let min = weights.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = weights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+        if max <= min {
+            return;
+        }
+
+        let step = (max - min) / (levels - 1) as f32;
+
+        for weight in self.weights_mut() {
+            let level = ((*weight - min) / step).round();
+            *weight = min + level * step;
+        }
+    }
+
+    /// Same as [`Network::quantize()`], but returns a quantized copy.
+    pub fn quantized(&self, levels: u32) -> Self {
+        let mut network = self.clone();
+        network.quantize(levels);
+        network
+    }
+
+    /// Returns the square root of the sum of squares of all the biases and
+    /// weights.
+    pub fn l2_norm(&self) -> f32 {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.weights())
+            .map(|weight| weight * weight)
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    /// Returns the sum of absolute values of all the biases and weights.
+    pub fn l1_norm(&self) -> f32 {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.weights())
+            .map(f32::abs)
+            .sum()
+    }
+
+    /// Returns the largest absolute value of all the weights (biases aside);
+    /// a cheap indicator of how sharply the network can react to its inputs.
+    pub fn max_abs_weight(&self) -> f32 {
+        self.layers
+            .iter()
+            .flat_map(|layer| &layer.neurons)
+            .flat_map(|neuron| &neuron.weights)
+            .fold(0.0, |max, weight| weight.abs().max(max))
+    }
+
+    /// Returns the product of each layer's largest absolute row sum (i.e.
+    /// the weights' infinity norm), which bounds how much the network can
+    /// amplify a change of its inputs - as long as the activations don't
+    /// amplify it themselves, which none of the built-in ones do.
+    pub fn spectral_estimate(&self) -> f32 {
+        self.layers
+            .iter()
+            .map(|layer| {
+                layer
+                    .neurons
+                    .iter()
+                    .map(|neuron| neuron.weights.iter().map(|weight| weight.abs()).sum::<f32>())
+                    .fold(0.0, f32::max)
+            })
+            .product()
+    }
+
+    /// Returns the number of neurons in each layer, starting with the inputs.
+    pub fn layer_sizes(&self) -> Vec<usize> {
+        let inputs = self.layers[0].neurons[0].weights.len();
+
+        std::iter::once(inputs)
+            .chain(self.layers.iter().map(|layer| layer.neurons.len()))
+            .collect()
+    }
+
+    /// Returns a copy of given layer's biases and weights, ordered the same
+    /// way as in [`Network::weights()`]; layer `0` is the first one past the
+    /// inputs.
+    pub fn layer_weights(&self, layer: usize) -> Option<Vec<f32>> {
+        let layer = self.layers.get(layer)?;
+
+        Some(layer.weights().collect())
+    }
+
+    /// Returns each layer's weights as an `outputs × inputs` matrix, row by
+    /// row (one row per neuron), along with the layer's biases - the same
+    /// shape most machine learning frameworks use.
+    pub fn weight_matrices(&self) -> Vec<(Vec<Vec<f32>>, Vec<f32>)> {
+        self.layers
+            .iter()
+            .map(|layer| {
+                let weights = layer.neurons.iter().map(|neuron| neuron.weights.clone()).collect();
+                let biases = layer.neurons.iter().map(|neuron| neuron.bias).collect();
+
+                (weights, biases)
+            })
+            .collect()
+    }
+
+    /// Returns the number of neurons in given layer, or zero if there's no
+    /// such layer; layers are indexed as in [`Network::layer_weights()`].
+    pub fn neuron_count(&self, layer: usize) -> usize {
+        self.layers
+            .get(layer)
+            .map_or(0, |layer| layer.neurons.len())
+    }
+
+    pub fn neuron_weights(&self, layer: usize, neuron: usize) -> Option<&[f32]> {
+        self.neuron(layer, neuron).map(|neuron| neuron.weights.as_slice())
+    }
+
+    pub fn neuron_bias(&self, layer: usize, neuron: usize) -> Option<f32> {
+        self.neuron(layer, neuron).map(|neuron| neuron.bias)
+    }
+
+    /// Returns how much each of the inputs contributes to given neuron's
+    /// output, i.e. the `weight * input` products before they're summed up
+    /// with the bias and activated; `inputs` are the inputs to that layer
+    /// (not to the whole network). `None` if there's no such neuron.
+    ///
+    /// Panics if the number of inputs doesn't match the neuron's weights.
+    pub fn neuron_contributions(
+        &self,
+        layer: usize,
+        neuron: usize,
+        inputs: &[f32],
+    ) -> Option<Vec<f32>> {
+        let neuron = self.neuron(layer, neuron)?;
+
+        assert_eq!(inputs.len(), neuron.weights.len());
+
+        Some(
+            inputs
+                .iter()
+                .zip(&neuron.weights)
+                .map(|(input, weight)| input * weight)
+                .collect()
+        )
+    }
+
+    /// Returns the activation given neuron applies - its own, if it's been
+    /// overridden, or its layer's otherwise.
+    pub fn neuron_activation(&self, layer: usize, neuron: usize) -> Option<Activation> {
+        let activation = self.layers.get(layer)?.activation;
+
+        self.neuron(layer, neuron)
+            .map(|neuron| neuron.activation.unwrap_or(activation))
+    }
+
+    /// Makes given neuron apply `activation` instead of its layer's one -
+    /// e.g. to have a single tanh output among sigmoid ones.
+    ///
+    /// Panics if there's no such neuron.
+    pub fn set_neuron_activation(&mut self, layer: usize, neuron: usize, activation: Activation) {
+        let neuron = self
+            .layers
+            .get_mut(layer)
+            .and_then(|layer| layer.neurons.get_mut(neuron))
+            .unwrap_or_else(|| panic!("there's no neuron #{} in layer #{}", neuron, layer));
+
+        neuron.activation = Some(activation);
+    }
+
+    fn neuron(&self, layer: usize, neuron: usize) -> Option<&Neuron> {
+        self.layers.get(layer)?.neurons.get(neuron)
     }
 
     pub fn propagate(&self, mut inputs: Vec<f32>) -> Vec<f32> {
+        if let Some(normalization) = &self.normalization {
+            normalization.apply(&mut inputs);
+        }
+
         for layer in &self.layers {
             inputs = layer.propagate(inputs);
         }
         inputs
     }
+
+    /// Same as [`Network::propagate()`], but first replaces NaN inputs with
+    /// zero and clamps every input into `range` - so that a single bogus
+    /// reading can't poison the whole output.
+    ///
+    /// Panics if `range` is reversed or either of its bounds is NaN.
+    pub fn propagate_clamped(&self, mut inputs: Vec<f32>, range: RangeInclusive<f32>) -> Vec<f32> {
+        // NaN fails any comparison, so it's rejected here as well
+        assert!(
+            range.start() <= range.end(),
+            "clamping range must not be reversed nor NaN, got {:?}", range
+        );
+
+        for input in &mut inputs {
+            if input.is_nan() {
+                *input = 0.0;
+            }
+
+            *input = input.clamp(*range.start(), *range.end());
+        }
+
+        self.propagate(inputs)
+    }
+
+    /// Same as [`Network::propagate()`], but also returns the index of the
+    /// largest output - the first one, if there are several.
+    pub fn propagate_argmax(&self, inputs: Vec<f32>) -> (Vec<f32>, usize) {
+        let outputs = self.propagate(inputs);
+
+        let argmax = (1..outputs.len()).fold(0, |best, id| {
+            if outputs[id] > outputs[best] { id } else { best }
+        });
+
+        (outputs, argmax)
+    }
+
+    /// Propagates each of the inputs, returning one output per input.
+    ///
+    /// Intermediate results are kept in buffers that are reused across the
+    /// whole batch.
+    pub fn propagate_batch(&self, inputs: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let input_size = self.layers[0].neurons[0].weights.len();
+
+        let mut current = Vec::new();
+        let mut next = Vec::new();
+
+        inputs
+            .iter()
+            .map(|input| {
+                assert_eq!(input.len(), input_size);
+
+                current.clear();
+                current.extend_from_slice(input);
+
+                if let Some(normalization) = &self.normalization {
+                    normalization.apply(&mut current);
+                }
+
+                for layer in &self.layers {
+                    layer.propagate_into(&current, &mut next);
+                    std::mem::swap(&mut current, &mut next);
+                }
+
+                current.clone()
+            })
+            .collect()
+    }
+
+    /// Returns the mean Euclidean distance between this and the other
+    /// network's outputs for each of the inputs, i.e. how differently the two
+    /// networks behave; no inputs means no divergence.
+    ///
+    /// Panics if the networks' layer sizes differ.
+    pub fn output_divergence(&self, other: &Network, inputs: &[Vec<f32>]) -> f32 {
+        assert_eq!(self.layer_sizes(), other.layer_sizes(), "networks' topologies differ");
+
+        if inputs.is_empty() {
+            return 0.0;
+        }
+
+        let total: f32 = self
+            .propagate_batch(inputs)
+            .iter()
+            .zip(other.propagate_batch(inputs))
+            .map(|(a, b)| {
+                a.iter()
+                    .zip(b)
+                    .map(|(a, b)| (a - b).powi(2))
+                    .sum::<f32>()
+                    .sqrt()
+            })
+            .sum();
+
+        total / inputs.len() as f32
+    }
+}
+
+impl InputNormalization {
+    fn apply(&self, inputs: &mut [f32]) {
+        assert_eq!(inputs.len(), self.means.len());
+
+        for ((input, mean), std) in inputs.iter_mut().zip(&self.means).zip(&self.stds) {
+            *input = (*input - mean) / std;
+        }
+    }
 }
 
 impl Layer {
+    fn weights(&self) -> impl Iterator<Item = f32> + '_ {
+        self.neurons
+            .iter()
+            .flat_map(|neuron| std::iter::once(&neuron.bias).chain(&neuron.weights))
+            .copied()
+    }
+
     fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
         self.neurons
             .iter()
-            .map(|neurou| neurou.propagate(&inputs))
+            .map(|neurou| neurou.propagate(&inputs, self.activation))
             .collect()
     }
-    pub fn random(input_neurons: usize, output_neurons: usize) -> Self {
-        let mut neurons = Vec::new();
-        let mut rng = rand::thread_rng();
-        for _ in 0..output_neurons {
-            neurons.push(Neuron::random(&mut rng, input_neurons));
-        }
 
-        Self { neurons }
+    fn propagate_into(&self, inputs: &[f32], outputs: &mut Vec<f32>) {
+        outputs.clear();
+        outputs.extend(self.neurons.iter().map(|neuron| neuron.propagate(inputs, self.activation)));
+    }
+
+    fn random(rng: &mut dyn RngCore, input_neurons: usize, output: LayerTopology) -> Self {
+        let neurons = (0..output.neurons)
+            .map(|_| Neuron::random(rng, input_neurons))
+            .collect();
+
+        Self { neurons, activation: output.activation.unwrap_or_default() }
+    }
+
+    fn from_weights(
+        input_neurons: usize,
+        output: LayerTopology,
+        weights: &mut dyn Iterator<Item = f32>
+    ) -> Self {
+        let neurons = (0..output.neurons)
+            .map(|_| Neuron::from_weights(input_neurons, weights))
+            .collect();
+
+        Self { neurons, activation: output.activation.unwrap_or_default() }
     }
 }
 
 impl Neuron {
-    fn propagate(&self, inputs: &[f32]) -> f32 {
+    fn propagate(&self, inputs: &[f32], activation: Activation) -> f32 {
 
         assert_eq!(inputs.len(), self.weights.len());
 
-        let mut output = 0.0;
+        let activation = self.activation.unwrap_or(activation);
 
-        output = inputs
+        let output = inputs
             .iter()
             .zip(&self.weights)
             .map(|(input, weight)| input * weight)
             .sum::<f32>();
     
-        (self.bias + output).max(0.0)
+        activation.apply(self.bias + output)
     }
-    pub fn random(rng: &mut dyn rand::RngCore, output_size: usize) -> Self {
+
+    fn random(rng: &mut dyn RngCore, output_size: usize) -> Self {
 
         let bias = rng.gen_range(-1.0..=1.0);
 
@@ -82,13 +562,25 @@ impl Neuron {
             .map(|_| rng.gen_range(-1.0..=1.0))
             .collect();
 
-        Self { bias, weights }
+        Self { bias, weights, activation: None }
     }
-}
 
+    fn from_weights(output_size: usize, weights: &mut dyn Iterator<Item = f32>) -> Self {
+        let bias = weights.next().expect("got not enough weights");
 
+        let weights = (0..output_size)
+            .map(|_| weights.next().expect("got not enough weights"))
+            .collect();
+
+        Self { bias, weights, activation: None }
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use rand_chacha::ChaCha8Rng;
+
     mod random {
         use super::*;
 
@@ -116,18 +608,611 @@ mod tests {
         fn test() {
             let neuron = Neuron {
                 bias: 0.5,
-                weights: vec![-0.3, 0.8]
+                weights: vec![-0.3, 0.8],
+                activation: None
             };
 
             approx::assert_relative_eq!(
-                neuron.propagate(&[-10.0, -10.0]),
+                neuron.propagate(&[-10.0, -10.0], Activation::Relu),
                 0.0
             );
 
             approx::assert_relative_eq!(
-                neuron.propagate(&[0.5, 1.0]),
+                neuron.propagate(&[0.5, 1.0], Activation::Relu),
                 (-0.3 * 0.5) + (0.8 * 1.0) + 0.5
             );
         }
     }
-}
\ No newline at end of file
+
+    mod propagate_argmax {
+        use super::*;
+
+        fn network() -> Network {
+            // Each output neuron scales the only input by its weight
+            Network::from_weights(
+                &[LayerTopology::new(1), LayerTopology::new(4)],
+                vec![0.0, 0.5, 0.0, 2.0, 0.0, 1.0, 0.0, 2.0],
+            )
+        }
+
+        #[test]
+        fn returns_the_dominant_output() {
+            let (outputs, argmax) = network().propagate_argmax(vec![1.0]);
+
+            assert_eq!(outputs, vec![0.5, 2.0, 1.0, 2.0]);
+            assert_eq!(argmax, 1);
+        }
+
+        #[test]
+        fn picks_the_first_of_equal_outputs() {
+            let (outputs, argmax) = network().propagate_argmax(vec![-1.0]);
+
+            assert_eq!(outputs, vec![0.0; 4]);
+            assert_eq!(argmax, 0);
+        }
+    }
+
+    mod propagate_clamped {
+        use super::*;
+
+        #[test]
+        fn sanitizes_the_inputs() {
+            let network = Network::from_weights(
+                &[LayerTopology::new(3), LayerTopology::new(2)],
+                vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8],
+            );
+
+            let outputs = network.propagate_clamped(vec![f32::NAN, 5.0, 0.5], -1.0..=1.0);
+
+            assert!(outputs.iter().all(|output| output.is_finite()));
+            assert_eq!(outputs, network.propagate(vec![0.0, 1.0, 0.5]));
+        }
+
+        #[test]
+        #[should_panic(expected = "clamping range must not be reversed nor NaN, got 1.0..=-1.0")]
+        fn panics_on_reversed_range() {
+            let network = Network::from_weights(
+                &[LayerTopology::new(1), LayerTopology::new(1)],
+                vec![0.1, 0.2],
+            );
+
+            network.propagate_clamped(vec![0.5], 1.0..=-1.0);
+        }
+    }
+
+    mod propagate_batch {
+        use super::*;
+
+        fn network() -> Network {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            Network::random(&mut rng, &[
+                LayerTopology::new(3),
+                LayerTopology::new(4),
+                LayerTopology::new(2),
+            ])
+        }
+
+        #[test]
+        fn matches_individual_propagation() {
+            let network = network();
+
+            let inputs = vec![
+                vec![0.1, 0.2, 0.3],
+                vec![-0.5, 1.0, 0.0],
+                vec![0.9, -0.4, 0.7],
+            ];
+
+            let actual = network.propagate_batch(&inputs);
+
+            assert_eq!(actual.len(), inputs.len());
+
+            for (actual, input) in actual.iter().zip(&inputs) {
+                let expected = network.propagate(input.clone());
+
+                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+            }
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_inputs_of_wrong_size() {
+            network().propagate_batch(&[vec![0.1, 0.2]]);
+        }
+    }
+
+    mod output_divergence {
+        use super::*;
+
+        fn network() -> Network {
+            Network::from_weights(
+                &[LayerTopology::new(2), LayerTopology::new(2)],
+                vec![0.1, 0.5, -0.2, 0.3, 0.4, 0.6],
+            )
+        }
+
+        fn inputs() -> Vec<Vec<f32>> {
+            vec![vec![1.0, 0.0], vec![0.5, 0.5], vec![0.0, 1.0]]
+        }
+
+        #[test]
+        fn network_does_not_diverge_from_itself() {
+            assert_eq!(network().output_divergence(&network(), &inputs()), 0.0);
+        }
+
+        #[test]
+        fn perturbed_network_diverges() {
+            let perturbed = Network::from_weights(
+                &[LayerTopology::new(2), LayerTopology::new(2)],
+                vec![0.1, 0.5, -0.2, 0.3, 0.4, 0.7],
+            );
+
+            // Only the second output changes, by 0.1 per unit of the second
+            // input: 0.0, 0.05 and 0.1 for the three inputs.
+            approx::assert_relative_eq!(
+                network().output_divergence(&perturbed, &inputs()),
+                0.05,
+                epsilon = 1e-6,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "networks' topologies differ")]
+        fn rejects_different_topologies() {
+            let other = Network::from_weights(
+                &[LayerTopology::new(2), LayerTopology::new(1)],
+                vec![0.1, 0.5, -0.2],
+            );
+
+            network().output_divergence(&other, &inputs());
+        }
+    }
+
+    mod weights {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let network = Network {
+                layers: vec![
+                    Layer {
+                        neurons: vec![Neuron {
+                            bias: 0.1,
+                            weights: vec![0.2, 0.3, 0.4],
+                            activation: None,
+                        }],
+                        activation: Activation::Relu,
+                    },
+                    Layer {
+                        neurons: vec![Neuron { bias: 0.5, weights: vec![0.6], activation: None }],
+                        activation: Activation::Relu,
+                    },
+                ],
+                normalization: None,
+            };
+
+            let actual = network.weights();
+            let expected = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+    }
+
+    mod layer_sizes {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let network = Network::random(&mut rng, &[
+                LayerTopology::new(3),
+                LayerTopology::new(6),
+                LayerTopology::new(2),
+            ]);
+
+            assert_eq!(network.layer_sizes(), vec![3, 6, 2]);
+        }
+    }
+
+    mod layer_weights {
+        use super::*;
+
+        fn network() -> Network {
+            Network::from_weights(
+                &[
+                    LayerTopology::new(2),
+                    LayerTopology::new(1),
+                    LayerTopology::new(1),
+                ],
+                vec![0.1, 0.2, 0.3, 0.4, 0.5],
+            )
+        }
+
+        #[test]
+        fn returns_given_layer() {
+            let network = network();
+
+            let actual = network.layer_weights(1).unwrap();
+            let expected = vec![0.4, 0.5];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn returns_none_for_out_of_range_layer() {
+            assert_eq!(network().layer_weights(2), None);
+        }
+    }
+
+    mod weight_matrices {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let network = Network::from_weights(
+                &[LayerTopology::new(2), LayerTopology::new(3)],
+                vec![0.1, 1.0, 2.0, 0.2, 3.0, 4.0, 0.3, 5.0, 6.0],
+            );
+
+            let matrices = network.weight_matrices();
+
+            assert_eq!(matrices.len(), 1);
+
+            let (weights, biases) = &matrices[0];
+
+            assert_eq!(weights, &vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+            assert_eq!(biases, &vec![0.1, 0.2, 0.3]);
+        }
+    }
+
+    mod from_weight_matrices {
+        use super::*;
+
+        fn layers() -> [LayerTopology; 3] {
+            [
+                LayerTopology::new(3),
+                LayerTopology::new(4),
+                LayerTopology::new(2),
+            ]
+        }
+
+        #[test]
+        fn round_trips_weight_matrices() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let network = Network::random(&mut rng, &layers());
+
+            let actual = Network::from_weight_matrices(&layers(), network.weight_matrices()).unwrap();
+
+            assert_eq!(actual.weights(), network.weights());
+        }
+
+        #[test]
+        fn rejects_wrong_layer_count() {
+            let matrices = vec![(vec![vec![1.0; 3]; 4], vec![0.0; 4])];
+
+            assert_eq!(
+                Network::from_weight_matrices(&layers(), matrices).unwrap_err(),
+                WeightError::LayerCountMismatch { expected: 2, actual: 1 },
+            );
+        }
+
+        #[test]
+        fn rejects_wrong_shapes() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let matrices = Network::random(&mut rng, &layers()).weight_matrices();
+
+            let mut ragged = matrices.clone();
+            ragged[1].0[1].pop();
+
+            let mut missing_bias = matrices;
+            missing_bias[0].1.pop();
+
+            assert_eq!(
+                Network::from_weight_matrices(&layers(), ragged).unwrap_err(),
+                WeightError::ShapeMismatch { layer: 1, rows: 2, columns: 4 },
+            );
+
+            assert_eq!(
+                Network::from_weight_matrices(&layers(), missing_bias).unwrap_err(),
+                WeightError::ShapeMismatch { layer: 0, rows: 4, columns: 3 },
+            );
+        }
+
+        #[test]
+        fn rejects_empty_layers() {
+            let layers = [LayerTopology::new(3), LayerTopology::new(0)];
+            let matrices = vec![(Vec::new(), Vec::new())];
+
+            assert_eq!(
+                Network::from_weight_matrices(&layers, matrices).unwrap_err(),
+                WeightError::EmptyLayer { layer: 1 },
+            );
+        }
+    }
+
+    mod neurons {
+        use super::*;
+
+        fn network() -> Network {
+            Network::from_weights(
+                &[
+                    LayerTopology::new(2),
+                    LayerTopology::new(2),
+                    LayerTopology::new(1),
+                ],
+                vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9],
+            )
+        }
+
+        #[test]
+        fn neuron_count() {
+            let network = network();
+
+            assert_eq!(network.neuron_count(0), 2);
+            assert_eq!(network.neuron_count(1), 1);
+            assert_eq!(network.neuron_count(2), 0);
+        }
+
+        #[test]
+        fn neuron_weights() {
+            let network = network();
+
+            assert_eq!(network.neuron_weights(0, 1), Some([0.5, 0.6].as_ref()));
+            assert_eq!(network.neuron_weights(1, 0), Some([0.8, 0.9].as_ref()));
+            assert_eq!(network.neuron_weights(0, 2), None);
+            assert_eq!(network.neuron_weights(2, 0), None);
+        }
+
+        #[test]
+        fn neuron_bias() {
+            let network = network();
+
+            assert_eq!(network.neuron_bias(0, 1), Some(0.4));
+            assert_eq!(network.neuron_bias(1, 0), Some(0.7));
+            assert_eq!(network.neuron_bias(1, 1), None);
+        }
+
+        #[test]
+        fn neuron_contributions() {
+            let network = network();
+
+            let contributions = network.neuron_contributions(0, 1, &[2.0, 3.0]).unwrap();
+            approx::assert_relative_eq!(contributions.as_slice(), [0.5 * 2.0, 0.6 * 3.0].as_ref());
+
+            assert_eq!(network.neuron_contributions(0, 2, &[2.0, -1.0]), None);
+            assert_eq!(network.neuron_contributions(2, 0, &[2.0, -1.0]), None);
+        }
+    }
+
+    mod from_weights {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let layers = &[
+                LayerTopology::new(3),
+                LayerTopology::new(2),
+            ];
+
+            let weights = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
+            let network = Network::from_weights(layers, weights.clone());
+
+            let actual = network.weights();
+
+            approx::assert_relative_eq!(actual.as_slice(), weights.as_slice());
+        }
+
+        #[test]
+        #[should_panic(expected = "layer 0 has no neurons")]
+        fn rejects_empty_layers() {
+            Network::from_weights(&[LayerTopology::new(0), LayerTopology::new(1)], vec![0.1]);
+        }
+    }
+
+    mod input_normalization {
+        use super::*;
+
+        fn network() -> Network {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            Network::random(&mut rng, &[
+                LayerTopology::new(2),
+                LayerTopology::new(3),
+                LayerTopology::new(2),
+            ])
+        }
+
+        #[test]
+        fn matches_manually_normalized_inputs() {
+            let normalized = network().with_input_normalization(vec![500.0, 0.5], vec![250.0, 0.25]);
+
+            let actual = normalized.propagate(vec![750.0, 0.25]);
+            let expected = network().propagate(vec![1.0, -1.0]);
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+
+            let actual = normalized.propagate_batch(&[vec![750.0, 0.25]]);
+
+            approx::assert_relative_eq!(actual[0].as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        #[should_panic(expected = "got 3 means for 2 inputs")]
+        fn rejects_means_of_wrong_length() {
+            network().with_input_normalization(vec![0.0; 3], vec![1.0; 2]);
+        }
+
+        #[test]
+        #[should_panic(expected = "got 1 stds for 2 inputs")]
+        fn rejects_stds_of_wrong_length() {
+            network().with_input_normalization(vec![0.0; 2], vec![1.0]);
+        }
+    }
+
+    mod activation {
+        use super::*;
+
+        #[test]
+        fn applies_each_layers_own_activation() {
+            let layers = &[
+                LayerTopology::new(1),
+                LayerTopology::new(2),
+                LayerTopology::new(1).with_activation(Activation::Tanh),
+            ];
+
+            // Hidden neurons: `-1 + 2x` and `1 - 2x`; output: `-3 + 2h0 + 2h1`
+            let weights = vec![-1.0, 2.0, 1.0, -2.0, -3.0, 2.0, 2.0];
+            let network = Network::from_weights(layers, weights.clone());
+
+            // Only one of the hidden neurons is non-negative, so ReLU passes
+            // through `|2x - 1|`
+            for x in [-2.0, 0.0, 0.5, 3.0] {
+                let hidden = (2.0f32 * x - 1.0).abs();
+                let actual = network.propagate(vec![x]);
+
+                approx::assert_relative_eq!(actual[0], (-3.0 + 2.0 * hidden).tanh());
+                assert!(actual[0] > -1.0 && actual[0] < 1.0);
+            }
+
+            assert_eq!(network.weights(), weights);
+        }
+
+        #[test]
+        fn applies_each_neurons_override() {
+            // Each output neuron passes the only input through
+            let mut network = Network::from_weights(
+                &[LayerTopology::new(1), LayerTopology::new(3)],
+                vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0],
+            );
+
+            network.set_neuron_activation(0, 1, Activation::Tanh);
+            network.set_neuron_activation(0, 2, Activation::Sigmoid);
+
+            assert_eq!(network.neuron_activation(0, 0), Some(Activation::Relu));
+            assert_eq!(network.neuron_activation(0, 1), Some(Activation::Tanh));
+            assert_eq!(network.neuron_activation(0, 3), None);
+
+            for x in [-2.0f32, 0.5, 3.0] {
+                let actual = network.propagate(vec![x]);
+
+                approx::assert_relative_eq!(actual[0], x.max(0.0));
+                approx::assert_relative_eq!(actual[1], x.tanh());
+                approx::assert_relative_eq!(actual[2], 1.0 / (1.0 + (-x).exp()));
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "there's no neuron #1 in layer #0")]
+        fn rejects_overriding_missing_neuron() {
+            let mut network = Network::from_weights(
+                &[LayerTopology::new(1), LayerTopology::new(1)],
+                vec![0.0, 1.0],
+            );
+
+            network.set_neuron_activation(0, 1, Activation::Tanh);
+        }
+    }
+
+    mod quantize {
+        use super::*;
+
+        fn network() -> Network {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            Network::random(&mut rng, &[
+                LayerTopology::new(3),
+                LayerTopology::new(4),
+                LayerTopology::new(2),
+            ])
+        }
+
+        fn distinct(mut weights: Vec<f32>) -> Vec<f32> {
+            weights.sort_by(f32::total_cmp);
+            weights.dedup();
+            weights
+        }
+
+        #[test]
+        fn two_levels_leave_the_extremes() {
+            let network = network();
+            let weights = network.weights();
+            let min = weights.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = weights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+            let mut quantized = network.clone();
+            quantized.quantize(2);
+
+            let actual = distinct(quantized.weights());
+            let expected = [min, max];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn rounds_to_the_nearest_level() {
+            let network = Network::from_weights(
+                &[LayerTopology::new(1), LayerTopology::new(2)],
+                vec![0.0, 0.3, 0.8, 1.0],
+            );
+
+            let actual = network.quantized(3).weights();
+
+            approx::assert_relative_eq!(actual.as_slice(), [0.0, 0.5, 1.0, 1.0].as_slice());
+        }
+
+        #[test]
+        #[should_panic(expected = "at least two levels")]
+        fn rejects_single_level() {
+            network().quantize(1);
+        }
+    }
+
+    mod norms {
+        use super::*;
+
+        fn network() -> Network {
+            Network::from_weights(
+                &[LayerTopology::new(1), LayerTopology::new(2)],
+                vec![1.0, -2.0, 2.0, -4.0],
+            )
+        }
+
+        #[test]
+        fn l2_norm() {
+            approx::assert_relative_eq!(network().l2_norm(), 5.0);
+        }
+
+        #[test]
+        fn l1_norm() {
+            approx::assert_relative_eq!(network().l1_norm(), 9.0);
+        }
+    }
+
+    mod stability {
+        use super::*;
+
+        fn network() -> Network {
+            Network::from_weights(
+                &[LayerTopology::new(2), LayerTopology::new(2), LayerTopology::new(1)],
+                vec![
+                    // Biases don't count, however large
+                    0.1, 1.0, -2.0,
+                    5.0, 0.5, 0.5,
+                    0.0, -3.0, 1.0,
+                ],
+            )
+        }
+
+        #[test]
+        fn max_abs_weight() {
+            approx::assert_relative_eq!(network().max_abs_weight(), 3.0);
+        }
+
+        #[test]
+        fn spectral_estimate() {
+            // Row sums: `max(1 + 2, 0.5 + 0.5)` times `3 + 1`
+            approx::assert_relative_eq!(network().spectral_estimate(), 3.0 * 4.0);
+        }
+    }
+}
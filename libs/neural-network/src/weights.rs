@@ -0,0 +1,176 @@
+use std::fmt;
+
+use crate::*;
+
+/// Flat list of biases and weights which is known to match a topology.
+///
+/// Unlike a plain `Vec<f32>`, it can only be created through
+/// [`Weights::for_topology()`], which checks the length up-front.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Weights {
+    topology: Vec<usize>,
+    weights: Vec<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WeightError {
+    LengthMismatch { expected: usize, actual: usize },
+
+    /// Got weight matrices for a different number of layers than the
+    /// topology has; see [`Network::from_weight_matrices()`].
+    LayerCountMismatch { expected: usize, actual: usize },
+
+    /// Given layer's weight matrix isn't `rows × columns`, or it doesn't
+    /// come with `rows` biases.
+    ShapeMismatch { layer: usize, rows: usize, columns: usize },
+
+    /// Given layer of the topology (`0` being the inputs) has no neurons.
+    EmptyLayer { layer: usize },
+
+    /// The topology doesn't have both an input and an output layer.
+    TooFewLayers { actual: usize },
+}
+
+impl Weights {
+    pub fn for_topology(
+        layers: &[LayerTopology],
+        genes: impl IntoIterator<Item = f32>
+    ) -> Result<Self, WeightError> {
+        check_layers(layers)?;
+
+        let weights: Vec<f32> = genes.into_iter().collect();
+        let expected = Network::weights_count(layers);
+
+        if weights.len() != expected {
+            return Err(WeightError::LengthMismatch { expected, actual: weights.len() });
+        }
+
+        let topology = layers.iter().map(|layer| layer.neurons).collect();
+
+        Ok(Self { topology, weights })
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        &self.weights
+    }
+
+    pub(crate) fn matches(&self, layers: &[LayerTopology]) -> bool {
+        self.topology.len() == layers.len()
+            && self.topology.iter().zip(layers).all(|(&neurons, layer)| neurons == layer.neurons)
+    }
+}
+
+/// Fails if there are less than two layers, or any of them has no neurons.
+pub(crate) fn check_layers(layers: &[LayerTopology]) -> Result<(), WeightError> {
+    if layers.len() < 2 {
+        return Err(WeightError::TooFewLayers { actual: layers.len() });
+    }
+
+    match layers.iter().position(|layer| layer.neurons == 0) {
+        Some(layer) => Err(WeightError::EmptyLayer { layer }),
+        None => Ok(()),
+    }
+}
+
+impl IntoIterator for Weights {
+    type Item = f32;
+
+    type IntoIter = std::vec::IntoIter<f32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.weights.into_iter()
+    }
+}
+
+impl fmt::Display for WeightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { expected, actual } => {
+                write!(f, "expected {} weights, got {}", expected, actual)
+            }
+
+            Self::LayerCountMismatch { expected, actual } => {
+                write!(f, "expected weights for {} layers, got {}", expected, actual)
+            }
+
+            Self::ShapeMismatch { layer, rows, columns } => {
+                write!(
+                    f,
+                    "expected layer {} to have a {}×{} weight matrix and {} biases",
+                    layer, rows, columns, rows
+                )
+            }
+
+            Self::EmptyLayer { layer } => {
+                write!(f, "layer {} has no neurons", layer)
+            }
+
+            Self::TooFewLayers { actual } => {
+                write!(f, "expected at least two layers, got {}", actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WeightError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers() -> [LayerTopology; 2] {
+        [
+            LayerTopology::new(2),
+            LayerTopology::new(2),
+        ]
+    }
+
+    #[test]
+    fn accepts_matching_length() {
+        let layers = layers();
+        let genes = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6];
+
+        let weights = Weights::for_topology(&layers, genes.clone()).unwrap();
+        let network = Network::from_validated_weights(&layers, weights);
+
+        let actual = network.weights();
+
+        approx::assert_relative_eq!(actual.as_slice(), genes.as_slice());
+    }
+
+    #[test]
+    fn rejects_length_mismatch() {
+        let actual = Weights::for_topology(&layers(), vec![0.1, 0.2, 0.3]);
+
+        assert_eq!(actual, Err(WeightError::LengthMismatch { expected: 6, actual: 3 }));
+    }
+
+    #[test]
+    fn rejects_empty_layers() {
+        let layers = [LayerTopology::new(2), LayerTopology::new(0), LayerTopology::new(1)];
+        let actual = Weights::for_topology(&layers, vec![0.1]);
+
+        assert_eq!(actual, Err(WeightError::EmptyLayer { layer: 1 }));
+    }
+
+    #[test]
+    fn rejects_less_than_two_layers() {
+        assert_eq!(
+            Weights::for_topology(&[], Vec::new()),
+            Err(WeightError::TooFewLayers { actual: 0 })
+        );
+
+        assert_eq!(
+            Weights::for_topology(&[LayerTopology::new(3)], Vec::new()),
+            Err(WeightError::TooFewLayers { actual: 1 })
+        );
+    }
+}
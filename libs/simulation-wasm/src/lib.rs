@@ -1,43 +1,784 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use lib_simulation as sim;
 use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+mod replay;
+
+pub use self::replay::*;
 
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct Simulation {
-    rng: ThreadRng,
-    sim: sim::Simulation
+    rng: ChaCha8Rng,
+    sim: sim::Simulation,
+    /// World as it was before the last step, if that step didn't start a
+    /// new generation.
+    previous: Option<World>,
+    recording: Option<Replay>
 }
 
 #[wasm_bindgen]
 impl Simulation {
     #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        let mut rng = thread_rng();
+        let mut rng = ChaCha8Rng::from_entropy();
         let sim = sim::Simulation::random(&mut rng);
     
-        Self { rng, sim }
+        Self { rng, sim, previous: None, recording: None }
+    }
+
+    pub fn with_config(config: JsValue) -> Result<Simulation, JsValue> {
+        let config: Config = from_js(&config)?;
+        let config = config.into_sim().map_err(|err| JsValue::from_str(&err))?;
+
+        let mut rng = ChaCha8Rng::from_entropy();
+        let sim = sim::Simulation::with_config(&mut rng, config);
+
+        Ok(Self { rng, sim, previous: None, recording: None })
     }
+
+    /// Replaces the random number generator with one seeded from `seed`, so
+    /// that everything from now on is reproducible; steps that have already
+    /// happened aren't affected.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+    }
+
+    /// Returns an independent copy of this simulation, random number
+    /// generator included, so that it can be stepped without affecting the
+    /// original.
+    pub fn fork(&self) -> Simulation {
+        self.clone()
+    }
+
     pub fn world(&self) -> JsValue {
         let world = World::from(self.sim.world());
-        JsValue::from_serde(&world).unwrap()
+        to_js(&world)
+    }
+
+    /// Animals packed into a `Float32Array`; see
+    /// [`sim::Simulation::world_packed()`] for the layout, except that the
+    /// positions are normalized, same as in `world()`.
+    pub fn world_packed(&self) -> Vec<f32> {
+        let (width, height) = (self.sim.world().width(), self.sim.world().height());
+        let mut packed = self.sim.world_packed();
+
+        for animal in packed[1..].chunks_mut(3) {
+            animal[0] /= width;
+            animal[1] /= height;
+        }
+
+        packed
+    }
+
+    /// World blended between the previous step (`alpha = 0.0`) and the
+    /// current one (`alpha = 1.0`), for rendering in-between steps; returns
+    /// the current world when there's no previous step to blend with.
+    pub fn world_interpolated(&self, alpha: f32) -> JsValue {
+        to_js(&self.interpolated(alpha))
+    }
+
+    /// Replaces the brain of `world().animals[index]` with one built from
+    /// `genes`, ordered the same way as `animal_brain(index).weights`.
+    pub fn set_animal_brain(&mut self, index: usize, genes: JsValue) -> Result<(), JsValue> {
+        let genes: Vec<f32> = from_js(&genes)?;
+        self.set_brain(index, genes).map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Scores a brain built from `genes` (see `set_animal_brain()`) without
+    /// affecting this simulation: on a fork, the brain replaces the first
+    /// animal's, which then lives for `steps` steps - or until the end of
+    /// the current generation, whichever comes first - and its fitness is
+    /// returned.
+    pub fn evaluate_brain(&self, genes: JsValue, steps: usize) -> Result<f32, JsValue> {
+        let genes: Vec<f32> = from_js(&genes)?;
+        self.evaluate(genes, steps).map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Best fitness of each completed generation, oldest first.
+    pub fn fitness_history(&self) -> JsValue {
+        to_js(&self.sim.fitness_history())
+    }
+
+    /// Fitness statistics of the most recent generations, oldest first; see
+    /// `Config.statistics_history_len`.
+    pub fn statistics_history(&self) -> JsValue {
+        to_js(&self.sim.statistics_history())
+    }
+
+    /// Genes of the fittest animal of each completed generation, oldest
+    /// first.
+    pub fn best_chromosomes(&self) -> JsValue {
+        let chromosomes: Vec<Vec<f32>> = self
+            .sim
+            .best_chromosomes()
+            .iter()
+            .map(|chromosome| chromosome.iter().copied().collect())
+            .collect();
+
+        to_js(&chromosomes)
+    }
+
+    /// Topology and weights of the brain of `world().animals[index]`.
+    pub fn animal_brain(&self, index: usize) -> Result<JsValue, JsValue> {
+        let brain = Brain::new(&self.sim, index).map_err(|err| JsValue::from_str(&err))?;
+        Ok(to_js(&brain))
+    }
+
+    /// Outputs of the brain of `world().animals[index]` for what it currently
+    /// sees, along with how they'd change its speed and rotation.
+    pub fn animal_decision(&self, index: usize) -> Result<JsValue, JsValue> {
+        let decision = Decision::new(&self.sim, index).map_err(|err| JsValue::from_str(&err))?;
+        Ok(to_js(&decision))
+    }
+
+    pub fn set_speed_gain(&mut self, speed_gain: f32) -> Result<(), JsValue> {
+        self.sim
+            .set_speed_gain(speed_gain)
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    pub fn set_rotation_gain(&mut self, rotation_gain: f32) -> Result<(), JsValue> {
+        self.sim
+            .set_rotation_gain(rotation_gain)
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    /// Gives the least fit `fraction` of the animals fresh, random brains.
+    pub fn cull(&mut self, fraction: f32) -> Result<(), JsValue> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(JsValue::from_str(&format!(
+                "cull fraction must be within [0, 1] (got {})", fraction
+            )));
+        }
+
+        self.sim.cull(&mut self.rng, fraction);
+        Ok(())
+    }
+
+    /// Changes the world's dimensions, rescaling everyone's positions into
+    /// the new bounds.
+    ///
+    /// The positions returned by `world()` and the like stay normalized to
+    /// `[0, 1]`, but the animals' speeds, eyesight and eating radius are in
+    /// world units - so e.g. in a world twice as wide, the animals take
+    /// twice as long to cross it and see a smaller part of it.
+    pub fn resize(&mut self, width: f32, height: f32) -> Result<(), JsValue> {
+        if !(width.is_finite() && width > 0.0 && height.is_finite() && height > 0.0) {
+            return Err(JsValue::from_str(&format!(
+                "world dimensions must be positive (got {}x{})", width, height
+            )));
+        }
+
+        self.sim.resize(width, height);
+        Ok(())
+    }
+
+    /// Captures the whole simulation, including the random number
+    /// generator's position, so that it can be resumed bit-exactly.
+    pub fn save_state(&self) -> JsValue {
+        to_js(&self.save())
+    }
+
+    /// Restores a state created by `save_state()`; the simulation has to use
+    /// the same configuration as the one the state was saved from.
+    pub fn load_state(&mut self, state: JsValue) -> Result<(), JsValue> {
+        let state: SaveState = from_js(&state)?;
+        self.load(state).map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Starts capturing the world after each step, beginning with the
+    /// current one; restarting discards the previous recording.
+    pub fn start_recording(&mut self) {
+        let mut recording = Replay::default();
+        recording.push(World::from(self.sim.world()));
+
+        self.recording = Some(recording);
+    }
+
+    /// Returns everything captured since `start_recording()`, to be played
+    /// back through `Replay`; `undefined` if not recording.
+    pub fn recording(&self) -> JsValue {
+        match &self.recording {
+            Some(recording) => to_js(recording),
+            None => JsValue::UNDEFINED,
+        }
+    }
+
+    /// Angle between where each animal last moved and where its brain
+    /// wanted it to head, in radians.
+    pub fn heading_errors(&self) -> JsValue {
+        to_js(&self.sim.heading_errors())
+    }
+
+    /// Returns `{ steps, generation }`: the number of steps taken during the
+    /// current generation and the number of generations completed so far.
+    pub fn age(&self) -> JsValue {
+        to_js(&Age::from(&self.sim))
+    }
+
+    /// Returns the index of the food each animal is focused on - the
+    /// nearest one it saw during the last step - or `null` if it saw none.
+    pub fn targets(&self) -> JsValue {
+        to_js(&self.sim.targets())
+    }
+
+    /// Picks a random animal, weighted by given metric: `"fitness"` (so far
+    /// in the current generation), `"energy"` or `"uniform"`; if all the
+    /// weights are zero, every animal is equally likely.
+    pub fn sample_animal_by(&mut self, metric: &str) -> Result<usize, JsValue> {
+        self.sample_animal(metric).map_err(|err| JsValue::from_str(&err))
     }
 
     pub fn step(&mut self) {
-        self.sim.step();
+        self.advance(1.0);
+    }
+
+    /// Same as `step()`, but advances the world by `dt` steps' worth of
+    /// time - e.g. to keep the animals' pace independent of the frame rate.
+    pub fn step_dt(&mut self, dt: f32) -> Result<(), JsValue> {
+        if !(dt.is_finite() && dt > 0.0) {
+            return Err(JsValue::from_str(&format!("dt must be positive (got {})", dt)));
+        }
+
+        self.advance(dt);
+        Ok(())
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+impl Simulation {
+    fn advance(&mut self, dt: f32) {
+        let generation = self.sim.generation();
+        let previous = World::from(self.sim.world());
+
+        self.sim.step_dt(&mut self.rng, dt);
+
+        // A new generation replaces all the animals, so there's nothing to
+        // interpolate from
+        self.previous = (self.sim.generation() == generation).then_some(previous);
+
+        if let Some(recording) = &mut self.recording {
+            recording.push(World::from(self.sim.world()));
+        }
+    }
+
+    fn interpolated(&self, alpha: f32) -> World {
+        let current = World::from(self.sim.world());
+
+        match &self.previous {
+            Some(previous) => previous.interpolate(&current, alpha),
+            None => current,
+        }
+    }
+
+    fn sample_animal(&mut self, metric: &str) -> Result<usize, String> {
+        let config = self.sim.config();
+        let animals = self.sim.world().animals();
+
+        let weights: Vec<f32> = match metric {
+            "fitness" => animals.iter().map(|animal| config.fitness(animal).max(0.0)).collect(),
+            "energy" => animals.iter().map(sim::Animal::energy).collect(),
+            "uniform" => vec![1.0; animals.len()],
+            _ => {
+                return Err(format!(
+                    "unknown metric `{}` (expected `fitness`, `energy` or `uniform`)",
+                    metric
+                ));
+            }
+        };
+
+        if animals.is_empty() {
+            return Err("there are no animals to sample from".into());
+        }
+
+        match rand::distributions::WeightedIndex::new(&weights) {
+            Ok(distribution) => Ok(distribution.sample(&mut self.rng)),
+            Err(_) => Ok(self.rng.gen_range(0..animals.len())),
+        }
+    }
+
+    fn evaluate(&self, genes: Vec<f32>, steps: usize) -> Result<f32, String> {
+        let mut fork = Self {
+            rng: self.rng.clone(),
+            sim: self.sim.clone(),
+            previous: None,
+            recording: None
+        };
+
+        fork.set_brain(0, genes)?;
+
+        // The generation's last step replaces everyone with their offspring
+        let remaining = fork.sim.config().generation_length - fork.sim.age() - 1;
+
+        for _ in 0..steps.min(remaining) {
+            fork.sim.step(&mut fork.rng);
+        }
+
+        let animal = &fork.sim.world().animals()[0];
+        Ok(fork.sim.config().fitness(animal))
+    }
+
+    fn set_brain(&mut self, index: usize, genes: Vec<f32>) -> Result<(), String> {
+        let animals = self.sim.world().animals().len();
+
+        if index >= animals {
+            return Err(format!(
+                "animal index {} is out of range (got {} animals)",
+                index, animals
+            ));
+        }
+
+        self.sim
+            .set_animal_brain(index, genes)
+            .map_err(|err| err.to_string())
+    }
+
+    fn save(&self) -> SaveState {
+        SaveState {
+            simulation: self.sim.save_state(),
+            rng: sim::RngState::new(&self.rng)
+        }
+    }
+
+    fn load(&mut self, state: SaveState) -> Result<(), String> {
+        let rng = state.rng
+            .restore()
+            .map_err(|err| err.to_string())?;
+
+        self.sim
+            .load_state(state.simulation)
+            .map_err(|err| err.to_string())?;
+
+        self.rng = rng;
+        self.previous = None;
+
+        Ok(())
+    }
+}
+
+#[allow(deprecated)]
+fn to_js<T: Serialize + ?Sized>(value: &T) -> JsValue {
+    JsValue::from_serde(value).unwrap()
+}
+
+#[allow(deprecated)]
+fn from_js<T: for<'a> Deserialize<'a>>(value: &JsValue) -> Result<T, JsValue> {
+    value
+        .into_serde()
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SaveState {
+    simulation: sim::SimulationState,
+    rng: sim::RngState
+}
+
+/// Simulation settings coming from JavaScript; missing fields fall back to
+/// the simulation's defaults.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub movement_model: Option<MovementModel>,
+    pub action_selection: Option<ActionSelection>,
+    pub fitness_objective: Option<FitnessObjective>,
+    pub spawn_layout: Option<SpawnLayout>,
+    pub food_layout: Option<FoodLayout>,
+    pub min_spawn_distance: Option<f32>,
+    pub food_count: Option<usize>,
+    pub energy_model: Option<EnergyModel>,
+    pub reproduction: Option<Reproduction>,
+    pub max_food_per_generation: Option<usize>,
+    pub fitness_decay: Option<f32>,
+    pub spacing_reward: Option<f32>,
+    pub age_penalty: Option<f32>,
+    pub statistics_history_len: Option<usize>,
+    pub generation_length: Option<usize>,
+    pub elitism: Option<usize>,
+    pub speed_gain: Option<f32>,
+    pub rotation_gain: Option<f32>,
+    pub max_speed: Option<f32>,
+    pub max_turn: Option<f32>,
+    pub environment_seed: Option<u64>,
+    pub eat_radius: Option<f32>,
+    pub eye_fov_range: Option<f32>,
+    pub eye_fov_angle: Option<f32>,
+    pub eye_cells: Option<usize>,
+    pub eye_sees_animals: Option<bool>,
+    pub eye_wraps_around: Option<bool>,
+    pub vision_encoding: Option<VisionEncoding>,
+    pub vision_noise: Option<f32>
+}
+
+impl Config {
+    fn into_sim(self) -> Result<sim::Config, String> {
+        let mut config = sim::Config::default();
+
+        if let Some(movement_model) = self.movement_model {
+            config.movement_model = movement_model.into();
+        }
+
+        if let Some(action_selection) = self.action_selection {
+            config.action_selection = action_selection.into();
+        }
+
+        if let Some(fitness_objective) = self.fitness_objective {
+            config.fitness_objective = fitness_objective.into();
+        }
+
+        if let Some(spawn_layout) = self.spawn_layout {
+            config.spawn_layout = spawn_layout.into();
+        }
+
+        if let Some(food_layout) = self.food_layout {
+            config.food_layout = food_layout.into();
+        }
+
+        if let Some(min_spawn_distance) = self.min_spawn_distance {
+            config.min_spawn_distance = min_spawn_distance;
+        }
+
+        if let Some(food_count) = self.food_count {
+            config.food_count = food_count;
+        }
+
+        if let Some(energy_model) = self.energy_model {
+            config.energy_model = energy_model.into();
+        }
+
+        if let Some(reproduction) = self.reproduction {
+            config.reproduction = reproduction.into();
+        }
+
+        if let Some(max_food_per_generation) = self.max_food_per_generation {
+            config.max_food_per_generation = Some(max_food_per_generation);
+        }
+
+        if let Some(fitness_decay) = self.fitness_decay {
+            config.fitness_decay = fitness_decay;
+        }
+
+        if let Some(spacing_reward) = self.spacing_reward {
+            config.spacing_reward = spacing_reward;
+        }
+
+        if let Some(age_penalty) = self.age_penalty {
+            config.age_penalty = age_penalty;
+        }
+
+        if let Some(statistics_history_len) = self.statistics_history_len {
+            config.statistics_history_len = statistics_history_len;
+        }
+
+        if let Some(generation_length) = self.generation_length {
+            config.generation_length = generation_length;
+        }
+
+        if let Some(elitism) = self.elitism {
+            config.elitism = elitism;
+        }
+
+        if let Some(speed_gain) = self.speed_gain {
+            config.speed_gain = speed_gain;
+        }
+
+        if let Some(rotation_gain) = self.rotation_gain {
+            config.rotation_gain = rotation_gain;
+        }
+
+        if let Some(max_speed) = self.max_speed {
+            config.max_speed = max_speed;
+        }
+
+        if let Some(max_turn) = self.max_turn {
+            config.max_turn = max_turn;
+        }
+
+        if let Some(environment_seed) = self.environment_seed {
+            config.environment_seed = Some(environment_seed);
+        }
+
+        if let Some(eat_radius) = self.eat_radius {
+            config.eat_radius = eat_radius;
+        }
+
+        if let Some(eye_fov_range) = self.eye_fov_range {
+            config.eye_fov_range = eye_fov_range;
+        }
+
+        if let Some(eye_fov_angle) = self.eye_fov_angle {
+            config.eye_fov_angle = eye_fov_angle;
+        }
+
+        if let Some(eye_cells) = self.eye_cells {
+            config.eye_cells = eye_cells;
+        }
+
+        if let Some(eye_sees_animals) = self.eye_sees_animals {
+            config.eye_sees_animals = eye_sees_animals;
+        }
+
+        if let Some(eye_wraps_around) = self.eye_wraps_around {
+            config.eye_wraps_around = eye_wraps_around;
+        }
+
+        if let Some(vision_encoding) = self.vision_encoding {
+            config.vision_encoding = vision_encoding.into();
+        }
+
+        if let Some(vision_noise) = self.vision_noise {
+            config.vision_noise = vision_noise;
+        }
+
+        config.validate().map_err(|err| err.to_string())?;
+
+        Ok(config)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum MovementModel {
+    SpeedRotation,
+    DifferentialDrive
+}
+
+impl From<MovementModel> for sim::MovementModel {
+    fn from(model: MovementModel) -> Self {
+        match model {
+            MovementModel::SpeedRotation => Self::SpeedRotation,
+            MovementModel::DifferentialDrive => Self::DifferentialDrive,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum ActionSelection {
+    Continuous,
+    Argmax
+}
+
+impl From<ActionSelection> for sim::ActionSelection {
+    fn from(selection: ActionSelection) -> Self {
+        match selection {
+            ActionSelection::Continuous => Self::Continuous,
+            ActionSelection::Argmax => Self::Argmax,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum FitnessObjective {
+    FoodEaten,
+    DistanceTraveled,
+    Combined { food_weight: f32, distance_weight: f32 }
+}
+
+impl From<FitnessObjective> for sim::FitnessObjective {
+    fn from(objective: FitnessObjective) -> Self {
+        match objective {
+            FitnessObjective::FoodEaten => Self::FoodEaten,
+            FitnessObjective::DistanceTraveled => Self::DistanceTraveled,
+            FitnessObjective::Combined { food_weight, distance_weight } => {
+                Self::Combined { food_weight, distance_weight }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum SpawnLayout {
+    Random,
+    Grid,
+    Circle
+}
+
+impl From<SpawnLayout> for sim::SpawnLayout {
+    fn from(layout: SpawnLayout) -> Self {
+        match layout {
+            SpawnLayout::Random => Self::Random,
+            SpawnLayout::Grid => Self::Grid,
+            SpawnLayout::Circle => Self::Circle,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum FoodLayout {
+    Random,
+    Grid,
+    Clustered
+}
+
+impl From<FoodLayout> for sim::FoodLayout {
+    fn from(layout: FoodLayout) -> Self {
+        match layout {
+            FoodLayout::Random => Self::Random,
+            FoodLayout::Grid => Self::Grid,
+            FoodLayout::Clustered => Self::Clustered,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum VisionEncoding {
+    Linear,
+    InverseSquare,
+    Binary
+}
+
+impl From<VisionEncoding> for sim::VisionEncoding {
+    fn from(encoding: VisionEncoding) -> Self {
+        match encoding {
+            VisionEncoding::Linear => Self::Linear,
+            VisionEncoding::InverseSquare => Self::InverseSquare,
+            VisionEncoding::Binary => Self::Binary,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum Reproduction {
+    Sexual,
+    Asexual
+}
+
+impl From<Reproduction> for sim::Reproduction {
+    fn from(reproduction: Reproduction) -> Self {
+        match reproduction {
+            Reproduction::Sexual => Self::Sexual,
+            Reproduction::Asexual => Self::Asexual,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum EnergyModel {
+    Unlimited,
+    Draining { drain: f32, food_gain: f32 }
+}
+
+impl From<EnergyModel> for sim::EnergyModel {
+    fn from(model: EnergyModel) -> Self {
+        match model {
+            EnergyModel::Unlimited => Self::Unlimited,
+            EnergyModel::Draining { drain, food_gain } => Self::Draining { drain, food_gain },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct World {
     pub animals: Vec<Animal>
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Animal {
+    /// Position within `[0, 1]`, regardless of the world's size.
     pub x: f32,
     pub y: f32,
-    pub rotation: f32
+    pub rotation: f32,
+    pub energy: f32
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Brain {
+    pub topology: Vec<usize>,
+    pub weights: Vec<f32>
+}
+
+impl Brain {
+    fn new(sim: &sim::Simulation, index: usize) -> Result<Self, String> {
+        let animals = sim.world().animals();
+
+        let animal = animals.get(index).ok_or_else(|| {
+            format!("animal index {} is out of range (got {} animals)", index, animals.len())
+        })?;
+
+        Ok(Self {
+            topology: animal.brain_topology(),
+            weights: animal.brain_weights()
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Age {
+    pub steps: usize,
+    pub generation: usize
+}
+
+impl From<&sim::Simulation> for Age {
+    fn from(sim: &sim::Simulation) -> Self {
+        Self { steps: sim.age(), generation: sim.generation() }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Decision {
+    pub outputs: Vec<f32>,
+    pub speed_delta: f32,
+    pub rotation_delta: f32
+}
+
+impl Decision {
+    fn new(sim: &sim::Simulation, index: usize) -> Result<Self, String> {
+        let decision = sim.animal_decision(index).ok_or_else(|| {
+            format!(
+                "animal index {} is out of range (got {} animals)",
+                index,
+                sim.world().animals().len()
+            )
+        })?;
+
+        Ok(Self {
+            outputs: decision.outputs,
+            speed_delta: decision.speed_delta,
+            rotation_delta: decision.rotation_delta
+        })
+    }
+}
+
+impl World {
+    /// Blends each animal between `self` and `next`, taking the shorter way
+    /// around the world's edges.
+    fn interpolate(&self, next: &World, alpha: f32) -> World {
+        let animals = self
+            .animals
+            .iter()
+            .zip(&next.animals)
+            .map(|(prev, next)| Animal {
+                x: lerp_wrapped(prev.x, next.x, alpha, 1.0),
+                y: lerp_wrapped(prev.y, next.y, alpha, 1.0),
+                rotation: lerp_angle(prev.rotation, next.rotation, alpha),
+                energy: next.energy
+            })
+            .collect();
+
+        World { animals }
+    }
+}
+
+fn lerp_wrapped(from: f32, to: f32, alpha: f32, size: f32) -> f32 {
+    let mut delta = to - from;
+
+    if delta > size / 2.0 {
+        delta -= size;
+    } else if delta < -size / 2.0 {
+        delta += size;
+    }
+
+    (from + alpha * delta).rem_euclid(size)
+}
+
+fn lerp_angle(from: f32, to: f32, alpha: f32) -> f32 {
+    let delta = to - from;
+    let delta = delta.sin().atan2(delta.cos());
+    let angle = from + alpha * delta;
+
+    angle.sin().atan2(angle.cos())
 }
 
 impl From<&sim::World> for World {
@@ -45,22 +786,334 @@ impl From<&sim::World> for World {
         let animals = world
             .animals()
             .iter()
-            .map(Animal::from)
+            .map(|animal| Animal {
+                x: animal.position().x / world.width(),
+                y: animal.position().y / world.height(),
+                rotation: animal.rotation().angle(),
+                energy: animal.energy()
+            })
             .collect();
-        println!("........World form");
-        
+
         Self { animals }
     }
 }
 
-impl From<&sim::Animal> for Animal {
-    fn from(animal: &sim::Animal) -> Self {
-        println!("...............");
-        println!("debug----------{}", animal.rotation().angle());
-        Self {
-            x: animal.position().x,
-            y: animal.position().y,
-            rotation: animal.rotation().angle()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod sample_animal {
+        use super::*;
+
+        #[test]
+        fn uniform_is_roughly_flat() {
+            let mut sim = Simulation::new();
+            sim.set_seed(0);
+
+            let animals = sim.sim.world().animals().len();
+            let mut counts = vec![0; animals];
+
+            for _ in 0..100 * animals {
+                counts[sim.sample_animal("uniform").unwrap()] += 1;
+            }
+
+            for (id, count) in counts.into_iter().enumerate() {
+                assert!((50..=150).contains(&count), "animal #{} got picked {} times", id, count);
+            }
+        }
+
+        #[test]
+        fn rejects_unknown_metrics() {
+            let mut sim = Simulation::new();
+            assert!(sim.sample_animal("charisma").unwrap_err().contains("charisma"));
         }
     }
-}
\ No newline at end of file
+
+    mod world_interpolated {
+        use super::*;
+
+        fn assert_worlds_eq(actual: &World, expected: &World) {
+            assert_eq!(actual.animals.len(), expected.animals.len());
+
+            for (actual, expected) in actual.animals.iter().zip(&expected.animals) {
+                approx::assert_abs_diff_eq!(actual.x, expected.x, epsilon = 1e-6);
+                approx::assert_abs_diff_eq!(actual.y, expected.y, epsilon = 1e-6);
+                approx::assert_abs_diff_eq!(actual.rotation, expected.rotation, epsilon = 1e-5);
+            }
+        }
+
+        #[test]
+        fn blends_between_previous_and_current_step() {
+            let mut sim = Simulation::new();
+            sim.step();
+
+            let previous = World::from(sim.sim.world());
+            sim.step();
+            let current = World::from(sim.sim.world());
+
+            assert_worlds_eq(&sim.interpolated(0.0), &previous);
+            assert_worlds_eq(&sim.interpolated(1.0), &current);
+        }
+
+        #[test]
+        fn returns_current_world_before_the_first_step() {
+            let sim = Simulation::new();
+            let current = World::from(sim.sim.world());
+
+            assert_worlds_eq(&sim.interpolated(0.0), &current);
+        }
+
+        #[test]
+        fn takes_the_shorter_way_around_the_edges() {
+            approx::assert_abs_diff_eq!(lerp_wrapped(0.9, 0.1, 0.5, 1.0), 0.0, epsilon = 1e-6);
+            approx::assert_abs_diff_eq!(lerp_wrapped(0.9, 0.1, 0.25, 1.0), 0.95, epsilon = 1e-6);
+        }
+    }
+
+    mod resize {
+        use super::*;
+
+        #[test]
+        fn keeps_positions_normalized() {
+            let mut sim = Simulation::new();
+            let before = World::from(sim.sim.world());
+
+            sim.sim.resize(4.0, 0.5);
+
+            let after = World::from(sim.sim.world());
+            let packed = sim.world_packed();
+
+            for (id, (before, after)) in before.animals.iter().zip(&after.animals).enumerate() {
+                approx::assert_relative_eq!(after.x, before.x, epsilon = 1e-6);
+                approx::assert_relative_eq!(after.y, before.y, epsilon = 1e-6);
+                approx::assert_relative_eq!(packed[1 + 3 * id], after.x);
+                approx::assert_relative_eq!(packed[2 + 3 * id], after.y);
+            }
+        }
+    }
+
+    mod fork {
+        use super::*;
+
+        #[test]
+        fn stepping_the_fork_leaves_the_original_intact() {
+            let mut original = Simulation::new();
+            original.step();
+
+            let before = World::from(original.sim.world());
+            let mut fork = original.fork();
+
+            for _ in 0..10 {
+                fork.step();
+            }
+
+            assert_eq!(World::from(original.sim.world()), before);
+            assert_ne!(World::from(fork.sim.world()), before);
+
+            for _ in 0..10 {
+                original.step();
+            }
+
+            assert_eq!(World::from(original.sim.world()), World::from(fork.sim.world()));
+        }
+    }
+
+    mod set_seed {
+        use super::*;
+
+        #[test]
+        fn same_seed_gives_same_behavior_from_then_on() {
+            let mut a = Simulation::new();
+            let mut b = a.fork();
+
+            // Makes `b`'s generator diverge from `a`'s
+            b.rng = ChaCha8Rng::from_entropy();
+
+            a.set_seed(42);
+            b.set_seed(42);
+
+            let generation = a.sim.generation();
+
+            while a.sim.generation() == generation {
+                a.step();
+                b.step();
+            }
+
+            a.step();
+            b.step();
+
+            assert_eq!(World::from(a.sim.world()), World::from(b.sim.world()));
+        }
+    }
+
+    mod save_state {
+        use super::*;
+
+        #[test]
+        fn restored_simulation_behaves_identically() {
+            let mut original = Simulation::new();
+
+            for _ in 0..10 {
+                original.step();
+            }
+
+            let state = original.save();
+
+            for _ in 0..10 {
+                original.step();
+            }
+
+            let mut restored = Simulation::new();
+            restored.load(state).unwrap();
+
+            for _ in 0..10 {
+                restored.step();
+            }
+
+            assert_eq!(restored.sim.save_state(), original.sim.save_state());
+            assert_eq!(restored.rng, original.rng);
+        }
+    }
+
+    mod brain {
+        use super::*;
+
+        #[test]
+        fn returns_weights_matching_the_topology() {
+            let sim = sim::Simulation::random(&mut thread_rng());
+            let brain = Brain::new(&sim, 0).unwrap();
+
+            let expected_weights: usize = brain
+                .topology
+                .windows(2)
+                .map(|layers| (layers[0] + 1) * layers[1])
+                .sum();
+
+            assert_eq!(brain.weights.len(), expected_weights);
+        }
+
+        #[test]
+        fn fails_for_out_of_range_index() {
+            let sim = sim::Simulation::random(&mut thread_rng());
+            let index = sim.world().animals().len();
+
+            assert!(Brain::new(&sim, index).is_err());
+        }
+    }
+
+    mod evaluate {
+        use super::*;
+
+        /// Builds genes for a brain that ignores its vision and always
+        /// picks `action`.
+        fn genes(sim: &Simulation, action: sim::Action) -> Vec<f32> {
+            let topology = sim.sim.world().animals()[0].brain_topology();
+            let logits = sim::Action::ALL.map(|output| if output == action { 1.0 } else { 0.0 });
+
+            sim::testing::constant_brain(&topology, &logits)
+        }
+
+        fn sim() -> Simulation {
+            let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+            let config = sim::Config {
+                action_selection: sim::ActionSelection::Argmax,
+                fitness_objective: sim::FitnessObjective::DistanceTraveled,
+                ..Default::default()
+            };
+
+            let sim = sim::Simulation::with_config(&mut rng, config);
+
+            Simulation { rng, sim, previous: None, recording: None }
+        }
+
+        #[test]
+        fn good_brain_scores_higher() {
+            let sim = sim();
+
+            let dashing = sim.evaluate(genes(&sim, sim::Action::Dash), 100).unwrap();
+            let idling = sim.evaluate(genes(&sim, sim::Action::Idle), 100).unwrap();
+
+            assert!(dashing > idling, "{} vs {}", dashing, idling);
+        }
+
+        #[test]
+        fn fails_for_wrong_gene_count() {
+            assert!(sim().evaluate(vec![0.0; 3], 10).is_err());
+        }
+    }
+
+    mod set_brain {
+        use super::*;
+
+        #[test]
+        fn replaces_the_animals_brain() {
+            let mut sim = Simulation::new();
+            let len = Brain::new(&sim.sim, 3).unwrap().weights.len();
+            let genes: Vec<f32> = (0..len).map(|gene| gene as f32 / 100.0).collect();
+
+            sim.set_brain(3, genes.clone()).unwrap();
+
+            assert_eq!(Brain::new(&sim.sim, 3).unwrap().weights, genes);
+        }
+
+        #[test]
+        fn fails_for_wrong_gene_count() {
+            let mut sim = Simulation::new();
+
+            assert!(sim.set_brain(0, vec![0.0; 3]).is_err());
+        }
+
+        #[test]
+        fn fails_for_out_of_range_index() {
+            let mut sim = Simulation::new();
+            let index = sim.sim.world().animals().len();
+
+            assert!(sim.set_brain(index, Vec::new()).is_err());
+        }
+    }
+
+    mod animal_decision {
+        use super::*;
+
+        #[test]
+        fn returns_one_output_per_output_neuron() {
+            let sim = sim::Simulation::random(&mut thread_rng());
+            let decision = Decision::new(&sim, 0).unwrap();
+            let brain = Brain::new(&sim, 0).unwrap();
+
+            assert_eq!(decision.outputs.len(), *brain.topology.last().unwrap());
+        }
+
+        #[test]
+        fn fails_for_out_of_range_index() {
+            let sim = sim::Simulation::random(&mut thread_rng());
+            let index = sim.world().animals().len();
+
+            assert!(Decision::new(&sim, index).is_err());
+        }
+    }
+
+    mod age {
+        use super::*;
+
+        #[test]
+        fn counts_steps_and_generations() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = sim::Config { generation_length: 3, ..Default::default() };
+            let mut sim = sim::Simulation::with_config(&mut rng, config);
+
+            let mut ages = vec![Age::from(&sim)];
+
+            for _ in 0..4 {
+                sim.step(&mut rng);
+                ages.push(Age::from(&sim));
+            }
+
+            let expected = [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1)]
+                .map(|(steps, generation)| Age { steps, generation });
+
+            assert_eq!(ages, expected);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+
+use crate::*;
+
+/// How many frames a recording keeps; older frames get dropped.
+const MAX_FRAMES: usize = 10_000;
+
+/// Sequence of worlds captured step by step, which can be played back
+/// without re-running the simulation.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Replay {
+    frames: VecDeque<World>
+}
+
+#[wasm_bindgen]
+impl Replay {
+    /// Loads a recording returned by `Simulation.recording()`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(recording: JsValue) -> Result<Replay, JsValue> {
+        from_js(&recording)
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns the `i`-th frame, or `undefined` if there's no such frame.
+    pub fn frame(&self, i: usize) -> JsValue {
+        match self.frames.get(i) {
+            Some(frame) => to_js(frame),
+            None => JsValue::UNDEFINED,
+        }
+    }
+}
+
+impl Replay {
+    pub(crate) fn push(&mut self, frame: World) {
+        if self.frames.len() == MAX_FRAMES {
+            self.frames.pop_front();
+        }
+
+        self.frames.push_back(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn final_frame_matches_the_live_world() {
+        let mut sim = Simulation::new();
+        sim.start_recording();
+
+        for _ in 0..25 {
+            sim.step();
+        }
+
+        let recording = sim.recording.as_ref().unwrap();
+
+        assert_eq!(recording.len(), 26);
+        assert_eq!(recording.frames.back(), Some(&World::from(sim.sim.world())));
+    }
+
+    #[test]
+    fn drops_oldest_frames_past_the_limit() {
+        let mut replay = Replay::default();
+
+        for _ in 0..=MAX_FRAMES {
+            replay.push(World { animals: Vec::new() });
+        }
+
+        assert_eq!(replay.len(), MAX_FRAMES);
+    }
+}
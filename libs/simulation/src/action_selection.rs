@@ -0,0 +1,157 @@
+use crate::*;
+
+/// How much a single [`Action::TurnLeft`] or [`Action::TurnRight`] rotates
+/// the animal.
+const TURN_ANGLE: f32 = PI / 8.0;
+
+/// Describes how the brain's outputs are turned into behavior.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ActionSelection {
+    /// The outputs control the movement directly, according to the
+    /// configured [`MovementModel`].
+    #[default]
+    Continuous,
+
+    /// Each output is a logit of one [`Action`]; the one with the highest
+    /// logit gets executed.
+    Argmax,
+}
+
+/// Discrete behavior chosen under [`ActionSelection::Argmax`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Move at the maximum speed.
+    Dash,
+    TurnLeft,
+    TurnRight,
+    /// Move at the minimum speed.
+    Idle,
+}
+
+impl ActionSelection {
+    /// Number of brain outputs this selection consumes.
+    pub fn outputs(&self, movement_model: MovementModel) -> usize {
+        match self {
+            Self::Continuous => movement_model.outputs(),
+            Self::Argmax => Action::ALL.len(),
+        }
+    }
+
+    pub(crate) fn apply(
+        &self,
+        movement_model: MovementModel,
+        animal: &mut Animal,
+        response: &[f32]
+    ) {
+        match self {
+            Self::Continuous => movement_model.apply(animal, response),
+            Self::Argmax => Action::from_logits(response).apply(animal),
+        }
+    }
+}
+
+impl Action {
+    /// All the actions, in the order of their logits.
+    pub const ALL: [Self; 4] = [Self::Dash, Self::TurnLeft, Self::TurnRight, Self::Idle];
+
+    /// Picks the action with the highest logit; ties go to the earlier one.
+    pub fn from_logits(logits: &[f32]) -> Self {
+        assert_eq!(logits.len(), Self::ALL.len());
+
+        let (id, _) = logits
+            .iter()
+            .enumerate()
+            .fold((0, f32::NEG_INFINITY), |best, (id, &logit)| {
+                if logit > best.1 { (id, logit) } else { best }
+            });
+
+        Self::ALL[id]
+    }
+
+    pub(crate) fn apply(&self, animal: &mut Animal) {
+        match self {
+            Self::Dash => animal.speed = SPEED_MAX,
+            Self::Idle => animal.speed = SPEED_MIN,
+
+            Self::TurnLeft => {
+                animal.rotation = na::Rotation2::new(animal.rotation.angle() + TURN_ANGLE);
+            }
+
+            Self::TurnRight => {
+                animal.rotation = na::Rotation2::new(animal.rotation.angle() - TURN_ANGLE);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    mod argmax {
+        use super::*;
+
+        /// Creates an animal whose brain ignores its vision and always
+        /// produces the highest logit for `action`.
+        fn animal(config: &Config, action: usize) -> Animal {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut animal = Animal::random(config, &mut rng);
+
+            let logits: Vec<_> = (0..Action::ALL.len())
+                .map(|id| if id == action { 1.0 } else { 0.0 })
+                .collect();
+
+            let weights = testing::constant_brain(&animal.brain_topology(), &logits);
+            animal.brain = Brain::from_weights(config, &animal.eye, weights).unwrap();
+            animal.rotation = na::Rotation2::new(0.0);
+            animal
+        }
+
+        #[test]
+        fn executes_action_with_the_largest_logit() {
+            let config = Config {
+                action_selection: ActionSelection::Argmax,
+                ..Default::default()
+            };
+
+            for (id, expected) in Action::ALL.into_iter().enumerate() {
+                let mut animal = animal(&config, id);
+                let response = animal.brain.propagate(vec![0.0; config.eye_cells]);
+
+                assert_eq!(Action::from_logits(&response), expected);
+
+                let speed = animal.speed;
+                config.action_selection.apply(config.movement_model, &mut animal, &response);
+
+                match expected {
+                    Action::Dash => assert_eq!(animal.speed, SPEED_MAX),
+                    Action::Idle => assert_eq!(animal.speed, SPEED_MIN),
+
+                    Action::TurnLeft => {
+                        assert_eq!(animal.speed, speed);
+                        approx::assert_relative_eq!(animal.rotation.angle(), TURN_ANGLE);
+                    }
+
+                    Action::TurnRight => {
+                        assert_eq!(animal.speed, speed);
+                        approx::assert_relative_eq!(animal.rotation.angle(), -TURN_ANGLE);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn sizes_the_output_layer_by_the_number_of_actions() {
+            let config = Config {
+                action_selection: ActionSelection::Argmax,
+                ..Default::default()
+            };
+
+            let animal = animal(&config, 0);
+
+            assert_eq!(animal.brain_topology().last(), Some(&Action::ALL.len()));
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use crate::*;
+
+#[derive(Clone, Debug)]
+pub struct Animal {
+    pub(crate) position: na::Point2<f32>,
+    pub(crate) rotation: na::Rotation2<f32>,
+    pub(crate) speed: f32,
+    pub(crate) eye: Eye,
+    pub(crate) brain: Brain,
+    /// Number of foods eaten during the current generation.
+    pub(crate) satiation: usize,
+    /// Same as `satiation`, but with older foods decayed according to
+    /// [`Config::fitness_decay`].
+    pub(crate) recent_food: f32,
+    /// Distance covered during the current generation.
+    pub(crate) distance: f32,
+    /// Distance to the nearest other animal, summed over the time spent
+    /// alive during the current generation; only tracked when
+    /// [`Config::spacing_reward`] is positive.
+    pub(crate) spacing: f32,
+    /// Time over which `spacing` has been summed, in steps.
+    pub(crate) spacing_time: f32,
+    /// Time spent alive during the current generation, in steps.
+    pub(crate) age: f32,
+    /// Remaining energy, from `0.0` (starved) to `1.0` (full); see
+    /// [`EnergyModel`].
+    pub(crate) energy: f32,
+    /// Direction the brain last wanted to head in, before any limits.
+    pub(crate) intended_heading: f32,
+    /// Angle between the last step's displacement and the intended heading.
+    pub(crate) heading_error: f32,
+    /// Index of the food the animal last saw nearest; see
+    /// [`Animal::target()`].
+    pub(crate) target: Option<usize>,
+}
+
+impl Animal {
+    pub fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
+        let eye = Eye::from_config(config);
+        let brain = Brain::random(rng, config, &eye);
+
+        Self::new(eye, brain, rng)
+    }
+
+    pub(crate) fn from_chromosome(
+        config: &Config,
+        chromosome: ga::Chromosome,
+        rng: &mut dyn RngCore
+    ) -> Self {
+        let eye = Eye::from_config(config);
+        let brain = Brain::from_chromosome(chromosome, config, &eye);
+
+        Self::new(eye, brain, rng)
+    }
+
+    fn new(eye: Eye, brain: Brain, rng: &mut dyn RngCore) -> Self {
+        let rotation: na::Rotation2<f32> = rng.gen();
+
+        Self {
+            position: rng.gen(),
+            rotation,
+            speed: 0.002,
+            eye,
+            brain,
+            satiation: 0,
+            recent_food: 0.0,
+            distance: 0.0,
+            spacing: 0.0,
+            spacing_time: 0.0,
+            age: 0.0,
+            energy: 1.0,
+            intended_heading: rotation.angle(),
+            heading_error: 0.0,
+            target: None,
+        }
+    }
+
+    pub fn position(&self) -> na::Point2<f32> {
+        self.position
+    }
+
+    pub fn rotation(&self) -> na::Rotation2<f32> {
+        self.rotation
+    }
+
+    pub fn satiation(&self) -> usize {
+        self.satiation
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    /// Mean distance to the nearest other animal during the current
+    /// generation, or zero if it's not been tracked; see
+    /// [`Config::spacing_reward`].
+    pub fn mean_spacing(&self) -> f32 {
+        if self.spacing_time > 0.0 {
+            self.spacing / self.spacing_time
+        } else {
+            0.0
+        }
+    }
+
+    /// Number of steps the animal's been alive for during the current
+    /// generation; fractional when stepping by custom time deltas.
+    pub fn age(&self) -> f32 {
+        self.age
+    }
+
+    pub fn energy(&self) -> f32 {
+        self.energy
+    }
+
+    /// Angle (in radians, within `[-π, π]`) between the direction the animal
+    /// last moved in and the direction its brain wanted it to head in; it's
+    /// nonzero when e.g. `max_turn` or `rotation_gain` get in the way.
+    pub fn heading_error(&self) -> f32 {
+        self.heading_error
+    }
+
+    /// Index (within [`World::foods()`]) of the nearest food the animal saw
+    /// when it last looked around, or `None` if it saw none.
+    pub fn target(&self) -> Option<usize> {
+        self.target
+    }
+
+    /// Whether the animal still has energy to move and eat.
+    pub fn is_alive(&self) -> bool {
+        self.energy > 0.0
+    }
+
+    /// Sizes of the brain's layers, from the inputs to the outputs.
+    pub fn brain_topology(&self) -> Vec<usize> {
+        self.brain.layer_sizes()
+    }
+
+    /// The brain's biases and weights, in the same order as the chromosome.
+    pub fn brain_weights(&self) -> Vec<f32> {
+        self.brain.weights()
+    }
+
+    /// Turns the brain's response into speed and rotation, as configured;
+    /// the turn is scaled by `config.rotation_gain` and limited to
+    /// `config.max_turn`, and the speed is limited to `config.max_speed`.
+    /// Both the turn and the change of speed are scaled by `dt`, which
+    /// mustn't exceed one step.
+    pub(crate) fn steer(&mut self, config: &Config, response: &[f32], dt: f32) {
+        let rotation = self.rotation.angle();
+        let speed = self.speed;
+
+        config.action_selection.apply(config.movement_model, self, response);
+
+        self.speed = (speed + (self.speed - speed) * dt).min(config.max_speed);
+
+        let turn = self.rotation.angle() - rotation;
+        let turn = turn.sin().atan2(turn.cos()) * dt;
+
+        self.intended_heading = rotation + turn;
+
+        let turn = turn * config.rotation_gain;
+        let max_turn = config.max_turn * dt;
+
+        if config.rotation_gain != 1.0 || dt != 1.0 || turn.abs() > max_turn {
+            self.rotation = na::Rotation2::new(rotation + turn.clamp(-max_turn, max_turn));
+        }
+    }
+
+    /// Moves the animal by its speed, scaled by `config.speed_gain` and
+    /// limited to `config.max_speed`, over `dt` steps' worth of time.
+    pub(crate) fn move_forward(&mut self, config: &Config, dt: f32, width: f32, height: f32) {
+        let step = (self.speed * config.speed_gain).min(config.max_speed) * dt;
+        let displacement = self.rotation * na::Vector2::new(step, 0.0);
+
+        if step > 0.0 {
+            let heading = displacement.y.atan2(displacement.x);
+            self.heading_error = na::wrap(heading - self.intended_heading, -PI, PI);
+        }
+
+        self.position += displacement;
+        self.distance += step;
+
+        self.position.x = na::wrap(self.position.x, 0.0, width);
+        self.position.y = na::wrap(self.position.y, 0.0, height);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::*;
+
+/// Adapts an [`Animal`] to the genetic algorithm: its brain becomes the
+/// chromosome and its score under the configured [`FitnessObjective`]
+/// (see [`Config::fitness()`]) becomes the fitness - floored at zero, since
+/// the roulette wheel can't weigh negative fitness (e.g. due to
+/// [`Config::age_penalty`]).
+pub struct AnimalIndividual {
+    fitness: f32,
+    chromosome: ga::Chromosome,
+}
+
+impl AnimalIndividual {
+    pub fn from_animal(config: &Config, animal: &Animal) -> Self {
+        Self {
+            fitness: config.fitness(animal).max(0.0),
+            chromosome: animal.brain.as_chromosome(),
+        }
+    }
+
+    pub fn into_animal(self, config: &Config, rng: &mut dyn RngCore) -> Animal {
+        Animal::from_chromosome(config, self.chromosome, rng)
+    }
+}
+
+impl ga::Individual for AnimalIndividual {
+    fn create(chromosome: ga::Chromosome) -> Self {
+        Self { fitness: 0.0, chromosome }
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn chromosome(&self) -> &ga::Chromosome {
+        &self.chromosome
+    }
+}
@@ -0,0 +1,52 @@
+use crate::*;
+
+#[derive(Clone, Debug)]
+pub struct Brain {
+    nn: nn::Network,
+}
+
+impl Brain {
+    pub fn random(rng: &mut dyn RngCore, config: &Config, eye: &Eye) -> Self {
+        Self { nn: nn::Network::random(rng, &Self::topology(config, eye)) }
+    }
+
+    pub fn from_chromosome(chromosome: ga::Chromosome, config: &Config, eye: &Eye) -> Self {
+        Self { nn: nn::Network::from_weights(&Self::topology(config, eye), chromosome) }
+    }
+
+    pub fn from_weights(
+        config: &Config,
+        eye: &Eye,
+        weights: Vec<f32>
+    ) -> Result<Self, nn::WeightError> {
+        let topology = Self::topology(config, eye);
+        let weights = nn::Weights::for_topology(&topology, weights)?;
+
+        Ok(Self { nn: nn::Network::from_validated_weights(&topology, weights) })
+    }
+
+    pub fn as_chromosome(&self) -> ga::Chromosome {
+        self.nn.weights().into_iter().collect()
+    }
+
+    pub fn weights(&self) -> Vec<f32> {
+        self.nn.weights()
+    }
+
+    /// Sizes of the layers, from the inputs to the outputs.
+    pub fn layer_sizes(&self) -> Vec<usize> {
+        self.nn.layer_sizes()
+    }
+
+    pub fn propagate(&self, vision: Vec<f32>) -> Vec<f32> {
+        self.nn.propagate(vision)
+    }
+
+    fn topology(config: &Config, eye: &Eye) -> [nn::LayerTopology; 3] {
+        [
+            nn::LayerTopology::new(eye.inputs()),
+            nn::LayerTopology::new(2 * eye.cells()),
+            nn::LayerTopology::new(config.brain_outputs()),
+        ]
+    }
+}
@@ -0,0 +1,428 @@
+use std::fmt;
+
+use crate::*;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub movement_model: MovementModel,
+    pub action_selection: ActionSelection,
+    pub fitness_objective: FitnessObjective,
+    pub spawn_layout: SpawnLayout,
+    pub food_layout: FoodLayout,
+    pub energy_model: EnergyModel,
+    pub reproduction: Reproduction,
+
+    /// How close randomly spawned animals may be to each other, in world
+    /// units; see [`SpawnLayout::Random`].
+    pub min_spawn_distance: f32,
+
+    /// How many foods there are in the world.
+    pub food_count: usize,
+
+    /// If set, eating more than this many foods during a generation doesn't
+    /// raise the fitness any further; see [`Config::fitness()`].
+    pub max_food_per_generation: Option<usize>,
+
+    /// How much the foods eaten so far lose of their worth towards the
+    /// fitness each step, within `[0, 1]`: with `0.0`, every food counts the
+    /// same, while with e.g. `0.001`, a food eaten 1000 steps ago counts
+    /// roughly a third as much as one eaten just now.
+    pub fitness_decay: f32,
+
+    /// How much each world unit of [mean spacing](Animal::mean_spacing)
+    /// adds to the fitness, on top of the objective's score; rewards
+    /// dispersed foraging when positive.
+    pub spacing_reward: f32,
+
+    /// How much fitness an animal loses per step it's been alive; see
+    /// [`Animal::age()`]. Keeps long-lived animals from dominating just by
+    /// outlasting the others.
+    pub age_penalty: f32,
+
+    /// How many of the last generations' statistics are kept; see
+    /// [`Simulation::statistics_history()`].
+    pub statistics_history_len: usize,
+
+    /// How many steps make up one generation.
+    pub generation_length: usize,
+
+    /// How many of the fittest brains are carried over unchanged into each
+    /// new generation.
+    pub elitism: usize,
+
+    /// Multiplies the animals' speed when moving them.
+    pub speed_gain: f32,
+    /// Multiplies the turns the brains make.
+    pub rotation_gain: f32,
+
+    /// How far an animal can move in one step, in world units.
+    pub max_speed: f32,
+    /// How much an animal can turn in one step, in radians.
+    pub max_turn: f32,
+
+    /// If set, spawn and food positions come from a generator reseeded with
+    /// this seed at the start of each generation, so that every generation
+    /// plays out in the same environment while the brains keep evolving;
+    /// otherwise they come from the generator passed to `step()`.
+    pub environment_seed: Option<u64>,
+
+    /// How close an animal has to get to a food to eat it, in world units.
+    pub eat_radius: f32,
+
+    /// How far the eye can see, in world units.
+    pub eye_fov_range: f32,
+    /// How wide the eye can see, in radians; must be within `(0, 2π]`.
+    pub eye_fov_angle: f32,
+    /// How many photoreceptors the eye has; each one becomes a brain input.
+    pub eye_cells: usize,
+    /// Whether the eye also perceives other animals, through a second set of
+    /// cells; doubles the brain's inputs.
+    pub eye_sees_animals: bool,
+    /// Whether the eye sees across the world's edges - since animals wrap
+    /// around them, the world is toroidal and food just across an edge is
+    /// actually close.
+    pub eye_wraps_around: bool,
+    /// How the distance to what the eye sees maps to its cells' activations.
+    pub vision_encoding: VisionEncoding,
+    /// Standard deviation of the Gaussian noise added to each eye cell
+    /// before it's fed to the brain; makes the brains more robust.
+    pub vision_noise: f32,
+}
+
+/// Describes which field of a [`Config`] is invalid and why.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigError {
+    pub field: &'static str,
+    pub reason: String,
+}
+
+impl Config {
+    /// Number of outputs the animals' brains have.
+    pub fn brain_outputs(&self) -> usize {
+        self.action_selection.outputs(self.movement_model)
+    }
+
+    /// Given animal's fitness under the configured objective, with the
+    /// foods eaten decayed by `fitness_decay` and capped at
+    /// `max_food_per_generation`, plus the `spacing_reward` and minus the
+    /// `age_penalty`.
+    pub fn fitness(&self, animal: &Animal) -> f32 {
+        let food = if self.fitness_decay > 0.0 {
+            animal.recent_food
+        } else {
+            animal.satiation as f32
+        };
+
+        let food = match self.max_food_per_generation {
+            Some(max_food) => food.min(max_food as f32),
+            None => food,
+        };
+
+        let spacing = if self.spacing_reward > 0.0 {
+            self.spacing_reward * animal.mean_spacing()
+        } else {
+            0.0
+        };
+
+        self.fitness_objective.score(food, animal.distance) + spacing
+            - self.age_penalty * animal.age
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.generation_length == 0 {
+            return Err(ConfigError::new("generation_length", "must be positive"));
+        }
+
+        if self.statistics_history_len == 0 {
+            return Err(ConfigError::new("statistics_history_len", "must be positive"));
+        }
+
+        self.fitness_objective
+            .validate()
+            .map_err(|reason| ConfigError::new("fitness_objective", reason))?;
+
+        if self.max_food_per_generation == Some(0) {
+            return Err(ConfigError::new("max_food_per_generation", "must be positive"));
+        }
+
+        if !(0.0..=1.0).contains(&self.fitness_decay) {
+            return Err(ConfigError::new(
+                "fitness_decay",
+                format!("must be within [0, 1], got {}", self.fitness_decay),
+            ));
+        }
+
+        if !(self.spacing_reward.is_finite() && self.spacing_reward >= 0.0) {
+            return Err(ConfigError::new(
+                "spacing_reward",
+                format!("must not be negative, got {}", self.spacing_reward),
+            ));
+        }
+
+        if !(self.age_penalty.is_finite() && self.age_penalty >= 0.0) {
+            return Err(ConfigError::new(
+                "age_penalty",
+                format!("must not be negative, got {}", self.age_penalty),
+            ));
+        }
+
+        self.energy_model
+            .validate()
+            .map_err(|reason| ConfigError::new("energy_model", reason))?;
+
+        if !(self.speed_gain.is_finite() && self.speed_gain > 0.0) {
+            return Err(ConfigError::new(
+                "speed_gain",
+                format!("must be positive, got {}", self.speed_gain),
+            ));
+        }
+
+        if !(self.rotation_gain.is_finite() && self.rotation_gain > 0.0) {
+            return Err(ConfigError::new(
+                "rotation_gain",
+                format!("must be positive, got {}", self.rotation_gain),
+            ));
+        }
+
+        if !(self.max_speed.is_finite() && self.max_speed > 0.0) {
+            return Err(ConfigError::new(
+                "max_speed",
+                format!("must be positive, got {}", self.max_speed),
+            ));
+        }
+
+        if !(self.max_turn.is_finite() && self.max_turn > 0.0) {
+            return Err(ConfigError::new(
+                "max_turn",
+                format!("must be positive, got {}", self.max_turn),
+            ));
+        }
+
+        if !(self.min_spawn_distance.is_finite() && self.min_spawn_distance >= 0.0) {
+            return Err(ConfigError::new(
+                "min_spawn_distance",
+                format!("must not be negative, got {}", self.min_spawn_distance),
+            ));
+        }
+
+        if !(self.eat_radius.is_finite() && self.eat_radius > 0.0) {
+            return Err(ConfigError::new(
+                "eat_radius",
+                format!("must be positive, got {}", self.eat_radius),
+            ));
+        }
+
+        if !(self.eye_fov_range.is_finite() && self.eye_fov_range > 0.0) {
+            return Err(ConfigError::new(
+                "eye_fov_range",
+                format!("must be positive, got {}", self.eye_fov_range),
+            ));
+        }
+
+        if !(self.eye_fov_angle > 0.0 && self.eye_fov_angle <= 2.0 * PI) {
+            return Err(ConfigError::new(
+                "eye_fov_angle",
+                format!("must be within (0, 2π], got {}", self.eye_fov_angle),
+            ));
+        }
+
+        if self.eye_cells == 0 {
+            return Err(ConfigError::new("eye_cells", "must be positive"));
+        }
+
+        if !(self.vision_noise.is_finite() && self.vision_noise >= 0.0) {
+            return Err(ConfigError::new(
+                "vision_noise",
+                format!("must not be negative, got {}", self.vision_noise),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            movement_model: Default::default(),
+            action_selection: Default::default(),
+            fitness_objective: Default::default(),
+            spawn_layout: Default::default(),
+            food_layout: Default::default(),
+            reproduction: Default::default(),
+            energy_model: Default::default(),
+            min_spawn_distance: 0.0,
+            food_count: 60,
+            max_food_per_generation: None,
+            fitness_decay: 0.0,
+            spacing_reward: 0.0,
+            age_penalty: 0.0,
+            statistics_history_len: 500,
+            generation_length: 2500,
+            elitism: 0,
+            speed_gain: 1.0,
+            rotation_gain: 1.0,
+            max_speed: SPEED_MAX,
+            max_turn: ROTATION_ACCEL,
+            environment_seed: None,
+            eat_radius: 0.01,
+            eye_fov_range: 0.25,
+            eye_fov_angle: PI + FRAC_PI_4,
+            eye_cells: 9,
+            eye_sees_animals: false,
+            eye_wraps_around: false,
+            vision_encoding: Default::default(),
+            vision_noise: 0.0,
+        }
+    }
+}
+
+impl ConfigError {
+    fn new(field: &'static str, reason: impl Into<String>) -> Self {
+        Self { field, reason: reason.into() }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid `{}`: {}", self.field, self.reason)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_valid() {
+        assert_eq!(Config::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_non_positive_eat_radius() {
+        for eat_radius in [0.0, -0.01, f32::INFINITY, f32::NAN] {
+            let config = Config { eat_radius, ..Default::default() };
+
+            assert_eq!(config.validate().unwrap_err().field, "eat_radius");
+        }
+    }
+
+    #[test]
+    fn rejects_negative_min_spawn_distance() {
+        for min_spawn_distance in [-0.01, f32::INFINITY, f32::NAN] {
+            let config = Config { min_spawn_distance, ..Default::default() };
+
+            assert_eq!(config.validate().unwrap_err().field, "min_spawn_distance");
+        }
+    }
+
+    #[test]
+    fn rejects_negative_or_non_finite_fitness_weights() {
+        let weights = [(-1.0, 1.0), (1.0, -1.0), (f32::NAN, 1.0), (1.0, f32::INFINITY)];
+
+        for (food_weight, distance_weight) in weights {
+            let config = Config {
+                fitness_objective: FitnessObjective::Combined { food_weight, distance_weight },
+                ..Default::default()
+            };
+
+            assert_eq!(config.validate().unwrap_err().field, "fitness_objective");
+        }
+
+        let config = Config {
+            fitness_objective: FitnessObjective::Combined { food_weight: 0.0, distance_weight: 2.0 },
+            ..Default::default()
+        };
+
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_zero_max_food_per_generation() {
+        let config = Config { max_food_per_generation: Some(0), ..Default::default() };
+        assert_eq!(config.validate().unwrap_err().field, "max_food_per_generation");
+    }
+
+    #[test]
+    fn caps_fitness_at_max_food_per_generation() {
+        use rand::SeedableRng;
+
+        let mut rng = rand_chacha::ChaCha8Rng::from_seed(Default::default());
+        let config = Config { max_food_per_generation: Some(4), ..Default::default() };
+
+        let mut animal = Animal::random(&config, &mut rng);
+        animal.satiation = 9;
+
+        assert_eq!(config.fitness(&animal), 4.0);
+        assert_eq!(Config::default().fitness(&animal), 9.0);
+    }
+
+    #[test]
+    fn rejects_fitness_decay_outside_of_unit_range() {
+        for fitness_decay in [-0.1, 1.1, f32::NAN] {
+            let config = Config { fitness_decay, ..Default::default() };
+
+            assert_eq!(config.validate().unwrap_err().field, "fitness_decay");
+        }
+    }
+
+    #[test]
+    fn rejects_negative_spacing_reward() {
+        for spacing_reward in [-0.1, f32::NAN] {
+            let config = Config { spacing_reward, ..Default::default() };
+
+            assert_eq!(config.validate().unwrap_err().field, "spacing_reward");
+        }
+    }
+
+    #[test]
+    fn rejects_negative_age_penalty() {
+        for age_penalty in [-0.1, f32::INFINITY, f32::NAN] {
+            let config = Config { age_penalty, ..Default::default() };
+
+            assert_eq!(config.validate().unwrap_err().field, "age_penalty");
+        }
+    }
+
+    #[test]
+    fn age_penalty_favors_younger_animals() {
+        use rand::SeedableRng;
+
+        let mut rng = rand_chacha::ChaCha8Rng::from_seed(Default::default());
+        let config = Config { age_penalty: 0.01, ..Default::default() };
+
+        let mut young = Animal::random(&config, &mut rng);
+        young.satiation = 5;
+        young.age = 100.0;
+
+        let mut old = young.clone();
+        old.age = 300.0;
+
+        approx::assert_relative_eq!(config.fitness(&young), 4.0);
+        approx::assert_relative_eq!(config.fitness(&old), 2.0);
+        assert_eq!(Config::default().fitness(&old), Config::default().fitness(&young));
+    }
+
+    #[test]
+    fn rejects_non_positive_movement_limits() {
+        let config = Config { max_speed: 0.0, ..Default::default() };
+        assert_eq!(config.validate().unwrap_err().field, "max_speed");
+
+        let config = Config { max_turn: -0.1, ..Default::default() };
+        assert_eq!(config.validate().unwrap_err().field, "max_turn");
+    }
+
+    #[test]
+    fn rejects_fov_angle_outside_of_full_circle() {
+        for eye_fov_angle in [0.0, -1.0, 2.0 * PI + 0.1, f32::NAN] {
+            let config = Config { eye_fov_angle, ..Default::default() };
+
+            assert_eq!(config.validate().unwrap_err().field, "eye_fov_angle");
+        }
+
+        let config = Config { eye_fov_angle: 2.0 * PI, ..Default::default() };
+        assert_eq!(config.validate(), Ok(()));
+    }
+}
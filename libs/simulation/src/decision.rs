@@ -0,0 +1,67 @@
+use crate::*;
+
+/// What an animal's brain makes of its current vision; see
+/// [`Simulation::animal_decision()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decision {
+    /// Raw outputs of the brain.
+    pub outputs: Vec<f32>,
+    /// How the outputs would change the animal's speed...
+    pub speed_delta: f32,
+    /// ... and its rotation, in radians within `(-π, π]`.
+    pub rotation_delta: f32,
+}
+
+impl Decision {
+    pub(crate) fn new(config: &Config, world: &World, id: usize) -> Self {
+        let animal = &world.animals[id];
+        let vision = animal.eye.sense(world, id);
+        let outputs = animal.brain.propagate(vision);
+
+        let mut moved = animal.clone();
+        moved.steer(config, &outputs, 1.0);
+
+        let rotation_delta = moved.rotation.angle() - animal.rotation.angle();
+
+        Self {
+            outputs,
+            speed_delta: moved.speed - animal.speed,
+            rotation_delta: rotation_delta.sin().atan2(rotation_delta.cos()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn matches_what_the_next_step_does() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+
+        let decision = sim.animal_decision(0).unwrap();
+        let before = sim.world().animals()[0].clone();
+
+        sim.step(&mut rng);
+
+        let after = &sim.world().animals()[0];
+
+        assert_eq!(decision.outputs.len(), *before.brain_topology().last().unwrap());
+        approx::assert_relative_eq!(before.speed + decision.speed_delta, after.speed);
+        approx::assert_relative_eq!(
+            (before.rotation.angle() + decision.rotation_delta).sin(),
+            after.rotation.angle().sin(),
+            epsilon = 1e-5
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_animal() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let sim = Simulation::random(&mut rng);
+
+        assert_eq!(sim.animal_decision(sim.world().animals().len()), None);
+    }
+}
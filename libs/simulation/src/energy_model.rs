@@ -0,0 +1,105 @@
+use crate::*;
+
+/// Describes whether animals spend energy, and so whether they can starve.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EnergyModel {
+    /// Animals never get tired and stay active for the whole generation.
+    #[default]
+    Unlimited,
+
+    /// Animals start with full energy (`1.0`), lose `drain` of it each step
+    /// and regain `food_gain` (up to full) for each food eaten; once their
+    /// energy runs out, they stop moving and eating until the next
+    /// generation.
+    Draining { drain: f32, food_gain: f32 },
+}
+
+impl EnergyModel {
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if let Self::Draining { drain, food_gain } = *self {
+            if !(drain.is_finite() && drain > 0.0) {
+                return Err(format!("drain must be positive, got {}", drain));
+            }
+
+            if !(food_gain.is_finite() && food_gain >= 0.0) {
+                return Err(format!("food gain must not be negative, got {}", food_gain));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn drain(&self, animal: &mut Animal, dt: f32) {
+        if let Self::Draining { drain, .. } = self {
+            animal.energy = (animal.energy - drain * dt).max(0.0);
+        }
+    }
+
+    pub(crate) fn feed(&self, animal: &mut Animal) {
+        if let Self::Draining { food_gain, .. } = self {
+            animal.energy = (animal.energy + food_gain).min(1.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn simulation(energy_model: EnergyModel) -> (Simulation, ChaCha8Rng) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let config = Config { generation_length: 100, energy_model, ..Default::default() };
+        let mut sim = Simulation::with_config(&mut rng, config);
+
+        sim.world.animals.truncate(1);
+        sim.world.foods.clear();
+
+        (sim, rng)
+    }
+
+    #[test]
+    fn starving_animal_runs_out_of_energy() {
+        let (mut sim, mut rng) = simulation(EnergyModel::Draining { drain: 0.125, food_gain: 0.5 });
+
+        for step in 1..=8 {
+            sim.step(&mut rng);
+
+            assert_eq!(sim.world().animals()[0].energy(), 1.0 - 0.125 * step as f32);
+        }
+
+        assert!(!sim.world().animals()[0].is_alive());
+
+        let position = sim.world().animals()[0].position();
+        sim.step(&mut rng);
+
+        assert_eq!(sim.world().animals()[0].position(), position);
+        assert_eq!(sim.world().animals()[0].energy(), 0.0);
+    }
+
+    #[test]
+    fn unlimited_energy_never_runs_out() {
+        let (mut sim, mut rng) = simulation(EnergyModel::Unlimited);
+
+        for _ in 0..50 {
+            sim.step(&mut rng);
+        }
+
+        assert_eq!(sim.world().animals()[0].energy(), 1.0);
+    }
+
+    #[test]
+    fn eating_refills_energy_up_to_full() {
+        let model = EnergyModel::Draining { drain: 0.125, food_gain: 0.5 };
+        let (mut sim, _) = simulation(model);
+        let animal = &mut sim.world.animals[0];
+
+        animal.energy = 0.25;
+        model.feed(animal);
+        assert_eq!(animal.energy, 0.75);
+
+        model.feed(animal);
+        assert_eq!(animal.energy, 1.0);
+    }
+}
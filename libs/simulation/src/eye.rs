@@ -0,0 +1,328 @@
+use crate::*;
+
+#[derive(Clone, Debug)]
+pub struct Eye {
+    fov_range: f32,
+    fov_angle: f32,
+    cells: usize,
+    /// Whether the eye has a second set of cells that perceive other
+    /// animals.
+    sees_animals: bool,
+    /// Whether the eye sees across the world's edges.
+    wraps_around: bool,
+    encoding: VisionEncoding,
+}
+
+impl Eye {
+    pub fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
+        assert!(fov_range > 0.0);
+        assert!(fov_angle > 0.0 && fov_angle <= 2.0 * PI);
+        assert!(cells > 0);
+
+        Self {
+            fov_range,
+            fov_angle,
+            cells,
+            sees_animals: false,
+            wraps_around: false,
+            encoding: VisionEncoding::default(),
+        }
+    }
+
+    /// Adds (or removes) the cells perceiving other animals.
+    pub fn with_animal_channel(mut self, sees_animals: bool) -> Self {
+        self.sees_animals = sees_animals;
+        self
+    }
+
+    /// Makes [`Eye::sense()`] perceive everything through the world's edges,
+    /// at its nearest wrapped-around position.
+    pub fn with_wrap_around(mut self, wraps_around: bool) -> Self {
+        self.wraps_around = wraps_around;
+        self
+    }
+
+    /// Changes how the distance to what the eye sees maps to its cells'
+    /// activations.
+    pub fn with_encoding(mut self, encoding: VisionEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.eye_fov_range, config.eye_fov_angle, config.eye_cells)
+            .with_animal_channel(config.eye_sees_animals)
+            .with_wrap_around(config.eye_wraps_around)
+            .with_encoding(config.vision_encoding)
+    }
+
+    pub fn fov_range(&self) -> f32 {
+        self.fov_range
+    }
+
+    pub fn fov_angle(&self) -> f32 {
+        self.fov_angle
+    }
+
+    pub fn cells(&self) -> usize {
+        self.cells
+    }
+
+    pub fn sees_animals(&self) -> bool {
+        self.sees_animals
+    }
+
+    pub fn wraps_around(&self) -> bool {
+        self.wraps_around
+    }
+
+    pub fn encoding(&self) -> VisionEncoding {
+        self.encoding
+    }
+
+    /// Number of values [`Eye::sense()`] returns, i.e. the brain's inputs.
+    pub fn inputs(&self) -> usize {
+        if self.sees_animals { 2 * self.cells } else { self.cells }
+    }
+
+    /// Returns what given animal of the world perceives: the food channel
+    /// (see [`Eye::process_vision()`]), followed by the same for the other
+    /// animals if this eye sees them.
+    pub(crate) fn sense(&self, world: &World, animal: usize) -> Vec<f32> {
+        self.sense_with_target(world, animal).0
+    }
+
+    /// Same as [`Eye::sense()`], but also returns the index of the food the
+    /// animal is focused on - the nearest one it sees - or `None` if it
+    /// doesn't see any.
+    pub(crate) fn sense_with_target(
+        &self,
+        world: &World,
+        animal: usize
+    ) -> (Vec<f32>, Option<usize>) {
+        let (position, rotation) = {
+            let animal = &world.animals[animal];
+            (animal.position, animal.rotation)
+        };
+
+        let bounds = self.wraps_around.then_some((world.width, world.height));
+        let foods = world.foods.iter().map(Food::position);
+
+        let (mut vision, target) = self.perceive(position, rotation, bounds, foods);
+
+        if self.sees_animals {
+            let others = world
+                .animals
+                .iter()
+                .enumerate()
+                .filter(|(id, _)| *id != animal)
+                .map(|(_, other)| other.position);
+
+            vision.extend(self.perceive(position, rotation, bounds, others).0);
+        }
+
+        (vision, target)
+    }
+
+    /// Returns how strongly each cell perceives food; closer food
+    /// produces a stronger signal, depending on the [`VisionEncoding`].
+    ///
+    /// Not knowing the world's size, this never sees across its edges; see
+    /// [`Eye::with_wrap_around()`].
+    pub fn process_vision(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        foods: &[Food]
+    ) -> Vec<f32> {
+        self.perceive(position, rotation, None, foods.iter().map(Food::position)).0
+    }
+
+    /// Returns the cells' activations, along with the index of the nearest
+    /// target seen (if any).
+    ///
+    /// When given the world's `bounds`, perceives each target at its
+    /// wrapped-around position nearest to `position`.
+    fn perceive(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        bounds: Option<(f32, f32)>,
+        targets: impl Iterator<Item = na::Point2<f32>>
+    ) -> (Vec<f32>, Option<usize>) {
+        let mut cells = vec![0.0; self.cells];
+        let mut nearest: Option<(usize, f32)> = None;
+
+        for (id, target) in targets.enumerate() {
+            if let Some((cell, dist)) = self.locate(position, rotation, bounds, target) {
+                cells[cell] += self.encoding.activation(dist, self.fov_range);
+
+                if nearest.is_none_or(|(_, nearest)| dist < nearest) {
+                    nearest = Some((id, dist));
+                }
+            }
+        }
+
+        (cells, nearest.map(|(id, _)| id))
+    }
+
+    /// Returns which cell sees `target`, and how far away it is, or `None`
+    /// if it lies outside of the field of view; see [`Eye::perceive()`].
+    fn locate(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        bounds: Option<(f32, f32)>,
+        target: na::Point2<f32>
+    ) -> Option<(usize, f32)> {
+        let mut vec = target - position;
+
+        if let Some((width, height)) = bounds {
+            vec.x = na::wrap(vec.x, -width / 2.0, width / 2.0);
+            vec.y = na::wrap(vec.y, -height / 2.0, height / 2.0);
+        }
+
+        let dist = vec.norm();
+
+        if dist >= self.fov_range {
+            return None;
+        }
+
+        let angle = na::Rotation2::rotation_between(&na::Vector2::x(), &vec).angle();
+        let angle = na::wrap(angle - rotation.angle(), -PI, PI);
+
+        if angle < -self.fov_angle / 2.0 || angle > self.fov_angle / 2.0 {
+            return None;
+        }
+
+        let angle = angle + self.fov_angle / 2.0;
+        let cell = angle / self.fov_angle * (self.cells as f32);
+        let cell = (cell as usize).min(self.cells - 1);
+
+        Some((cell, dist))
+    }
+}
+
+/// Adds a sample from `N(0, std²)` to each cell; doesn't touch the cells (or
+/// the generator) at all when `std` is zero.
+pub(crate) fn add_vision_noise(vision: &mut [f32], std: f32, rng: &mut dyn RngCore) {
+    if std == 0.0 {
+        return;
+    }
+
+    for cell in vision {
+        *cell += std * ga::standard_normal(rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn cell_count_sizes_vision_and_brain_inputs() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let foods = [Food { position: na::Point2::new(0.6, 0.5) }];
+
+        for eye_cells in [9, 13] {
+            let config = Config { eye_cells, ..Default::default() };
+            let animal = Animal::random(&config, &mut rng);
+
+            let vision = animal.eye.process_vision(
+                na::Point2::new(0.5, 0.5),
+                na::Rotation2::new(0.0),
+                &foods,
+            );
+
+            assert_eq!(vision.len(), eye_cells);
+            assert_eq!(animal.brain_topology()[0], eye_cells);
+        }
+    }
+
+    #[test]
+    fn animal_channel_doubles_vision_and_brain_inputs() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        for eye_sees_animals in [false, true] {
+            let config = Config { eye_sees_animals, ..Default::default() };
+            let world = World::random(&config, &mut rng);
+            let expected = if eye_sees_animals { 2 } else { 1 } * config.eye_cells;
+
+            let animal = &world.animals()[0];
+
+            assert_eq!(animal.eye.sense(&world, 0).len(), expected);
+            assert_eq!(animal.brain_topology()[0], expected);
+        }
+    }
+
+    #[test]
+    fn animal_channel_perceives_other_animals() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let config = Config { eye_sees_animals: true, ..Default::default() };
+        let mut world = World::random(&config, &mut rng);
+
+        world.foods.clear();
+        world.animals.truncate(2);
+        world.animals[0].position = na::Point2::new(0.5, 0.5);
+        world.animals[0].rotation = na::Rotation2::new(0.0);
+        world.animals[1].position = na::Point2::new(0.6, 0.5);
+
+        let vision = world.animals[0].eye.sense(&world, 0);
+        let (foods, animals) = vision.split_at(config.eye_cells);
+
+        assert!(foods.iter().all(|&cell| cell == 0.0));
+        assert!(animals.iter().any(|&cell| cell > 0.0));
+    }
+
+    #[test]
+    fn wrapping_eye_sees_across_the_edges() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        for eye_wraps_around in [false, true] {
+            let config = Config { eye_wraps_around, ..Default::default() };
+            let mut world = World::random(&config, &mut rng);
+
+            world.animals.truncate(1);
+            world.animals[0].position = na::Point2::new(0.95, 0.5);
+            world.animals[0].rotation = na::Rotation2::new(0.0);
+            world.foods = vec![Food { position: na::Point2::new(0.05, 0.5) }];
+
+            let vision = world.animals[0].eye.sense(&world, 0);
+            let strongest = vision.iter().cloned().fold(0.0, f32::max);
+
+            if eye_wraps_around {
+                // 0.1 away, with the range of 0.25
+                approx::assert_relative_eq!(strongest, 0.6, epsilon = 1e-4);
+            } else {
+                assert_eq!(strongest, 0.0);
+            }
+        }
+    }
+
+    mod add_vision_noise {
+        use super::*;
+
+        fn noisy(std: f32) -> Vec<f32> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut vision = vec![0.0, 0.5, 1.0];
+
+            add_vision_noise(&mut vision, std, &mut rng);
+            vision
+        }
+
+        #[test]
+        fn zero_noise_leaves_vision_unchanged() {
+            assert_eq!(noisy(0.0), vec![0.0, 0.5, 1.0]);
+        }
+
+        #[test]
+        fn positive_noise_changes_vision_reproducibly() {
+            let vision = noisy(0.1);
+
+            assert_ne!(vision, vec![0.0, 0.5, 1.0]);
+            assert_eq!(vision, noisy(0.1));
+        }
+    }
+}
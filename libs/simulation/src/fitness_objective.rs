@@ -0,0 +1,106 @@
+use crate::*;
+
+/// Describes what the animals are rewarded for when evolving.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FitnessObjective {
+    /// Number of foods eaten during the generation.
+    #[default]
+    FoodEaten,
+
+    /// Distance covered during the generation.
+    DistanceTraveled,
+
+    /// Weighted sum of the foods eaten and the distance covered.
+    Combined {
+        food_weight: f32,
+        distance_weight: f32,
+    },
+}
+
+impl FitnessObjective {
+    /// Scores the animal by all the foods it's eaten and the distance it's
+    /// covered; see [`Config::fitness()`] for the configurable variant.
+    pub fn fitness(&self, animal: &Animal) -> f32 {
+        self.score(animal.satiation as f32, animal.distance)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if let Self::Combined { food_weight, distance_weight } = *self {
+            if !(food_weight.is_finite() && food_weight >= 0.0) {
+                return Err(format!("food weight must not be negative, got {}", food_weight));
+            }
+
+            if !(distance_weight.is_finite() && distance_weight >= 0.0) {
+                return Err(format!(
+                    "distance weight must not be negative, got {}",
+                    distance_weight
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Scores given amount of food eaten and distance covered.
+    pub fn score(&self, food: f32, distance: f32) -> f32 {
+        match *self {
+            Self::FoodEaten => food,
+            Self::DistanceTraveled => distance,
+            Self::Combined { food_weight, distance_weight } => {
+                ga::weighted_sum(&[food, distance], &[food_weight, distance_weight])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn animal(rng: &mut dyn RngCore, speed: f32) -> Animal {
+        let mut animal = Animal::random(&Config::default(), rng);
+        animal.speed = speed;
+
+        for _ in 0..10 {
+            animal.move_forward(&Config::default(), 1.0, 1.0, 1.0);
+        }
+
+        animal
+    }
+
+    mod distance_traveled {
+        use super::*;
+
+        #[test]
+        fn moving_animal_scores_higher_than_stationary_one() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let moving = animal(&mut rng, 0.004);
+            let stationary = animal(&mut rng, 0.0);
+
+            let objective = FitnessObjective::DistanceTraveled;
+
+            approx::assert_relative_eq!(objective.fitness(&moving), 0.04);
+            approx::assert_relative_eq!(objective.fitness(&stationary), 0.0);
+        }
+    }
+
+    mod combined {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut animal = animal(&mut rng, 0.004);
+            animal.satiation = 3;
+
+            let objective = FitnessObjective::Combined {
+                food_weight: 2.0,
+                distance_weight: 10.0,
+            };
+
+            approx::assert_relative_eq!(objective.fitness(&animal), 2.0 * 3.0 + 10.0 * 0.04);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::*;
+
+#[derive(Clone, Debug)]
+pub struct Food {
+    pub(crate) position: na::Point2<f32>,
+}
+
+impl Food {
+    pub fn random(rng: &mut dyn RngCore) -> Self {
+        Self {
+            position: rng.gen()
+        }
+    }
+
+    pub fn position(&self) -> na::Point2<f32> {
+        self.position
+    }
+}
@@ -0,0 +1,106 @@
+use crate::*;
+
+/// How many clusters [`FoodLayout::Clustered`] groups the foods into.
+const CLUSTERS: usize = 4;
+
+/// How far a food can lie from its cluster's center, relative to the
+/// world's size.
+const CLUSTER_RADIUS: f32 = 0.1;
+
+/// Describes where the foods are placed at the beginning of each generation;
+/// eaten foods always respawn at random positions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FoodLayout {
+    /// Each food is placed at a random position.
+    #[default]
+    Random,
+
+    /// Foods are placed in the centers of a regular grid's cells, row by
+    /// row - the same way every time.
+    Grid,
+
+    /// Foods are scattered around a few random spots.
+    Clustered,
+}
+
+impl FoodLayout {
+    pub(crate) fn arrange(
+        &self,
+        foods: &mut [Food],
+        width: f32,
+        height: f32,
+        rng: &mut dyn RngCore
+    ) {
+        let count = foods.len();
+
+        match self {
+            Self::Random => {
+                for food in foods {
+                    food.position = world::random_position(rng, width, height);
+                }
+            }
+
+            Self::Grid => {
+                for (id, food) in foods.iter_mut().enumerate() {
+                    food.position = spawn_layout::grid_position(id, count, width, height);
+                }
+            }
+
+            Self::Clustered => {
+                let centers: Vec<_> = (0..CLUSTERS)
+                    .map(|_| world::random_position(rng, width, height))
+                    .collect();
+
+                for (id, food) in foods.iter_mut().enumerate() {
+                    let center = centers[id % CLUSTERS];
+                    let dx = rng.gen_range(-CLUSTER_RADIUS..=CLUSTER_RADIUS) * width;
+                    let dy = rng.gen_range(-CLUSTER_RADIUS..=CLUSTER_RADIUS) * height;
+
+                    food.position = na::Point2::new(
+                        na::wrap(center.x + dx, 0.0, width),
+                        na::wrap(center.y + dy, 0.0, height),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn positions(layout: FoodLayout, seed: u64) -> Vec<na::Point2<f32>> {
+        let config = Config { food_layout: layout, food_count: 30, ..Default::default() };
+        let sim = Simulation::with_config(&mut ChaCha8Rng::seed_from_u64(seed), config);
+
+        sim.world().foods().iter().map(Food::position).collect()
+    }
+
+    #[test]
+    fn grid_is_the_same_regardless_of_seed() {
+        let positions = positions(FoodLayout::Grid, 1);
+
+        assert_eq!(positions.len(), 30);
+        assert_eq!(positions, self::positions(FoodLayout::Grid, 2));
+    }
+
+    #[test]
+    fn random_and_clustered_depend_on_seed() {
+        for layout in [FoodLayout::Random, FoodLayout::Clustered] {
+            let positions = positions(layout, 1);
+
+            assert_eq!(positions.len(), 30);
+            assert_ne!(positions, self::positions(layout, 2));
+        }
+    }
+
+    #[test]
+    fn clustered_keeps_foods_within_bounds() {
+        for position in positions(FoodLayout::Clustered, 1) {
+            assert!((0.0..=1.0).contains(&position.x));
+            assert!((0.0..=1.0).contains(&position.y));
+        }
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Summary of the animals' fitness at the end of a generation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GenerationStatistics {
+    /// Number of generations completed before this one.
+    pub generation: usize,
+    pub min_fitness: f32,
+    pub max_fitness: f32,
+    pub mean_fitness: f32,
+}
+
+impl GenerationStatistics {
+    /// Panics if there are no fitness values.
+    pub(crate) fn new(generation: usize, fitness: &[f32]) -> Self {
+        assert!(!fitness.is_empty(), "got an empty population");
+
+        Self {
+            generation,
+            min_fitness: fitness.iter().copied().fold(f32::INFINITY, f32::min),
+            max_fitness: fitness.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            mean_fitness: fitness.iter().sum::<f32>() / fitness.len() as f32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let stats = GenerationStatistics::new(7, &[2.0, 0.0, 4.0]);
+
+        assert_eq!(stats.generation, 7);
+        assert_eq!(stats.min_fitness, 0.0);
+        assert_eq!(stats.max_fitness, 4.0);
+        approx::assert_relative_eq!(stats.mean_fitness, 2.0);
+    }
+}
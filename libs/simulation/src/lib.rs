@@ -1,86 +1,1147 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use genetic_algorithm as ga;
 use nalgebra as na;
-use rand::{Rng, RngCore};
-pub struct Simulation {
-    world: World
-}
+use neural_network as nn;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
-pub struct World {
-    animals: Vec<Animal>,
-    foods: Vec<Food>
-}
+mod action_selection;
+mod animal;
+mod animal_individual;
+mod brain;
+mod config;
+mod decision;
+mod energy_model;
+mod eye;
+mod fitness_objective;
+mod food;
+mod food_layout;
+mod generation_statistics;
+mod movement_model;
+mod reproduction;
+mod ring_buffer;
+mod spawn_layout;
+mod state;
+mod vision_encoding;
+mod world;
 
-pub struct Animal {
-    position: na::Point2<f32>,
-    rotation: na::Rotation2<f32>,
-    speed: f32
-}
-pub struct Food {
-    position: na::Point2<f32>,
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+pub use self::{
+    action_selection::*,
+    animal::*,
+    brain::*,
+    config::*,
+    decision::*,
+    energy_model::*,
+    eye::*,
+    fitness_objective::*,
+    food::*,
+    food_layout::*,
+    generation_statistics::*,
+    movement_model::*,
+    reproduction::*,
+    spawn_layout::*,
+    state::*,
+    vision_encoding::*,
+    world::*,
+};
+
+use self::{animal_individual::*, ring_buffer::*};
+
+/// Minimum and maximum speed an animal can move at.
+const SPEED_MIN: f32 = 0.001;
+const SPEED_MAX: f32 = 0.005;
+
+/// How much the brain can change the speed and the rotation in one step.
+const SPEED_ACCEL: f32 = 0.2;
+const ROTATION_ACCEL: f32 = FRAC_PI_2;
+
+/// How many past generations are kept in the fitness history.
+const FITNESS_HISTORY_LEN: usize = 500;
+
+/// How many past generations' best chromosomes are kept.
+const BEST_CHROMOSOMES_LEN: usize = 100;
+
+#[derive(Clone)]
+pub struct Simulation {
+    config: Config,
+    world: World,
+    ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
+    age: usize,
+    /// Fraction of a step accumulated by [`Simulation::step_dt()`] that
+    /// hasn't yet counted towards `age`.
+    step_fraction: f32,
+    generation: usize,
+    fitness_history: RingBuffer<f32>,
+    statistics_history: RingBuffer<GenerationStatistics>,
+    best_chromosomes: RingBuffer<ga::Chromosome>,
+    /// Drives spawn and food positions when `config.environment_seed` is
+    /// set.
+    environment_rng: Option<ChaCha8Rng>,
 }
 
 impl Simulation {
     pub fn random(rng: &mut dyn RngCore) -> Self {
-        Self { world: World::random(rng) }
+        Self::with_config(rng, Config::default())
     }
+
+    /// Panics if the config is invalid; see [`Config::validate()`].
+    pub fn with_config(rng: &mut dyn RngCore, config: Config) -> Self {
+        if let Err(err) = config.validate() {
+            panic!("{}", err);
+        }
+
+        let ga = config.reproduction.genetic_algorithm(config.elitism);
+
+        let mut sim = Self {
+            world: World::random(&config, rng),
+            statistics_history: RingBuffer::new(config.statistics_history_len),
+            config,
+            ga,
+            age: 0,
+            step_fraction: 0.0,
+            generation: 0,
+            fitness_history: RingBuffer::new(FITNESS_HISTORY_LEN),
+            best_chromosomes: RingBuffer::new(BEST_CHROMOSOMES_LEN),
+            environment_rng: None,
+        };
+
+        if sim.config.environment_seed.is_some() {
+            sim.reset_environment(rng);
+        }
+
+        sim
+    }
+
+    /// Creates a simulation seeded with `seed` whose animals get brains built
+    /// from `chromosomes` (cycling through them if there are fewer than
+    /// animals), except for `random_fraction` of them, which get fresh,
+    /// random brains - handy for resuming training without losing diversity.
+    ///
+    /// Panics if the config is invalid, if `random_fraction` lies outside of
+    /// `[0, 1]`, or if some brains are to be seeded but `chromosomes` is
+    /// empty.
+    pub fn from_chromosomes_with_random(
+        config: Config,
+        chromosomes: &[ga::Chromosome],
+        random_fraction: f32,
+        seed: u64,
+    ) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&random_fraction),
+            "random fraction must be within [0, 1]"
+        );
+
+        let mut sim = Self::with_config(&mut ChaCha8Rng::seed_from_u64(seed), config);
+
+        let animals = &mut sim.world.animals;
+        let random = (random_fraction * animals.len() as f32).round() as usize;
+        let seeded = animals.len() - random;
+
+        assert!(
+            seeded == 0 || !chromosomes.is_empty(),
+            "at least one chromosome is required to seed the brains"
+        );
+
+        let chromosomes = chromosomes.iter().cycle();
+
+        for (animal, chromosome) in animals.iter_mut().take(seeded).zip(chromosomes) {
+            animal.brain = Brain::from_chromosome(chromosome.clone(), &sim.config, &animal.eye);
+        }
+
+        sim
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
     pub fn world(&self) -> &World {
         &self.world
     }
 
-    pub fn step(&mut self) {
+    /// Returns the animals packed into a flat buffer, cheaper to transfer
+    /// than one object per animal: the number of animals, followed by each
+    /// animal's `x`, `y` and `rotation` (in radians), i.e.
+    /// `[n, x0, y0, rotation0, x1, y1, rotation1, ...]`.
+    pub fn world_packed(&self) -> Vec<f32> {
+        let animals = self.world.animals();
+        let mut packed = Vec::with_capacity(1 + 3 * animals.len());
+
+        packed.push(animals.len() as f32);
+
+        for animal in animals {
+            packed.extend([animal.position.x, animal.position.y, animal.rotation.angle()]);
+        }
+
+        packed
+    }
+
+    /// Changes how strongly the speed translates into movement; see
+    /// [`Config::speed_gain`].
+    pub fn set_speed_gain(&mut self, speed_gain: f32) -> Result<(), ConfigError> {
+        self.update_config(Config { speed_gain, ..self.config.clone() })
+    }
+
+    /// Changes how strongly the brains' turns translate into rotation; see
+    /// [`Config::rotation_gain`].
+    pub fn set_rotation_gain(&mut self, rotation_gain: f32) -> Result<(), ConfigError> {
+        self.update_config(Config { rotation_gain, ..self.config.clone() })
+    }
+
+    fn update_config(&mut self, config: Config) -> Result<(), ConfigError> {
+        config.validate()?;
+        self.config = config;
+        Ok(())
+    }
+
+    /// Changes the world's dimensions; see [`World::resize()`].
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.world.resize(width, height);
+    }
+
+    /// Gives the least fit `fraction` of the animals (by their fitness so
+    /// far) fresh, random brains, leaving everything else as it is.
+    ///
+    /// Panics if `fraction` lies outside of `[0, 1]`.
+    pub fn cull(&mut self, rng: &mut dyn RngCore, fraction: f32) {
+        assert!((0.0..=1.0).contains(&fraction), "cull fraction must be within [0, 1]");
+
+        let animals = &mut self.world.animals;
+        let count = (fraction * animals.len() as f32).round() as usize;
+
+        let mut weakest: Vec<_> = (0..animals.len()).collect();
+
+        weakest.sort_by(|&a, &b| {
+            let a = self.config.fitness(&animals[a]);
+            let b = self.config.fitness(&animals[b]);
+
+            a.total_cmp(&b)
+        });
+
+        for id in weakest.into_iter().take(count) {
+            let animal = &mut animals[id];
+            animal.brain = Brain::random(rng, &self.config, &animal.eye);
+        }
+    }
+
+    /// Captures everything needed to resume this simulation exactly; since
+    /// the random number generator is owned by the caller, its state has to
+    /// be saved separately.
+    pub fn save_state(&self) -> SimulationState {
+        SimulationState::new(self)
+    }
+
+    /// Restores a state created by [`Simulation::save_state()`]; the state's
+    /// brains have to match this simulation's configuration.
+    pub fn load_state(&mut self, state: SimulationState) -> Result<(), StateError> {
+        state.restore(self)
+    }
+
+    /// Number of steps taken during the current generation.
+    pub fn age(&self) -> usize {
+        self.age
+    }
+
+    /// Number of generations completed so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Best fitness of each completed generation, oldest first; only the
+    /// last `FITNESS_HISTORY_LEN` generations are kept.
+    pub fn fitness_history(&self) -> Vec<f32> {
+        self.fitness_history.iter().copied().collect()
+    }
+
+    /// Statistics of the last `config.statistics_history_len` completed
+    /// generations, oldest first.
+    pub fn statistics_history(&self) -> Vec<GenerationStatistics> {
+        self.statistics_history.iter().copied().collect()
+    }
+
+    /// Chromosome of the fittest animal of each completed generation, oldest
+    /// first; only the last `BEST_CHROMOSOMES_LEN` generations are kept.
+    pub fn best_chromosomes(&self) -> Vec<ga::Chromosome> {
+        self.best_chromosomes.iter().cloned().collect()
+    }
+
+    /// Replaces given animal's brain with one built from `weights`, ordered
+    /// as in [`Animal::brain_weights()`]; fails if there's a wrong number of
+    /// them.
+    ///
+    /// Panics if there's no such animal.
+    pub fn set_animal_brain(
+        &mut self,
+        animal: usize,
+        weights: Vec<f32>,
+    ) -> Result<(), nn::WeightError> {
+        let animal = &mut self.world.animals[animal];
+        animal.brain = Brain::from_weights(&self.config, &animal.eye, weights)?;
+
+        Ok(())
+    }
+
+    /// Each animal's heading error; see [`Animal::heading_error()`].
+    pub fn heading_errors(&self) -> Vec<f32> {
+        self.world.animals.iter().map(Animal::heading_error).collect()
+    }
+
+    /// Each animal's target food; see [`Animal::target()`].
+    pub fn targets(&self) -> Vec<Option<usize>> {
+        self.world.animals.iter().map(Animal::target).collect()
+    }
+
+    /// What given animal's brain decides based on what it currently sees,
+    /// without actually moving it; `None` if there's no such animal.
+    pub fn animal_decision(&self, animal: usize) -> Option<Decision> {
+        if animal >= self.world.animals.len() {
+            return None;
+        }
+
+        Some(Decision::new(&self.config, &self.world, animal))
+    }
+
+    pub fn step(&mut self, rng: &mut dyn RngCore) {
+        self.step_dt(rng, 1.0);
+    }
+
+    /// Same as [`Simulation::step()`], but advances the world by `dt` steps'
+    /// worth of time: the distance moved, the turns and changes of speed, the
+    /// energy drained and the fitness decay are all scaled by `dt`, so e.g.
+    /// two `step_dt(0.5)` calls end up close to one `step()`. A `dt` longer
+    /// than a step is split into equal substeps of at most one step each, so
+    /// that no animal can jump over a food. The generation's age counts whole
+    /// steps.
+    ///
+    /// Panics if `dt` isn't positive.
+    pub fn step_dt(&mut self, rng: &mut dyn RngCore, dt: f32) {
+        assert!(dt.is_finite() && dt > 0.0, "dt must be positive, got {}", dt);
+
+        let substeps = dt.ceil();
+
+        for _ in 0..substeps as usize {
+            self.substep(rng, dt / substeps);
+        }
+    }
+
+    /// Advances the world by `dt` steps' worth of time, at most one step.
+    fn substep(&mut self, rng: &mut dyn RngCore, dt: f32) {
+        self.process_collisions(rng, dt);
+        self.process_brains(rng, dt);
+        self.process_movements(dt);
+        self.process_spacing(dt);
+        self.process_energy(dt);
+
+        self.step_fraction += dt;
+
+        while self.step_fraction >= 1.0 {
+            self.step_fraction -= 1.0;
+            self.age += 1;
+        }
+
+        if self.age >= self.config.generation_length {
+            self.evolve(rng);
+        }
+    }
+
+    /// Fast-forwards to the end of the current generation.
+    pub fn train(&mut self, rng: &mut dyn RngCore) {
+        let generation = self.generation;
+
+        while self.generation == generation {
+            self.step(rng);
+        }
+    }
+
+    fn process_collisions(&mut self, rng: &mut dyn RngCore, dt: f32) {
+        let world = &mut self.world;
+
+        let rng = environment_rng(&mut self.environment_rng, rng);
+
+        for animal in &mut world.animals {
+            animal.recent_food *= (1.0 - self.config.fitness_decay).powf(dt);
+        }
+
+        for animal in world.animals.iter_mut().filter(|animal| animal.is_alive()) {
+            for food in &mut world.foods {
+                let distance = na::distance(&animal.position, &food.position);
+
+                if distance <= self.config.eat_radius {
+                    animal.satiation += 1;
+                    animal.recent_food += 1.0;
+                    self.config.energy_model.feed(animal);
+                    food.position = world::random_position(rng, world.width, world.height);
+                }
+            }
+        }
+    }
+
+    fn process_brains(&mut self, rng: &mut dyn RngCore, dt: f32) {
+        // Steering doesn't move anyone, so everyone can look around first
+        let sights: Vec<_> = self
+            .world
+            .animals
+            .iter()
+            .enumerate()
+            .map(|(id, animal)| animal.eye.sense_with_target(&self.world, id))
+            .collect();
+
+        for (animal, (mut vision, target)) in self.world.animals.iter_mut().zip(sights) {
+            animal.target = target;
+
+            if !animal.is_alive() {
+                continue;
+            }
+
+            eye::add_vision_noise(&mut vision, self.config.vision_noise, rng);
+
+            let response = animal.brain.propagate(vision);
+
+            animal.steer(&self.config, &response, dt);
+        }
+    }
+
+    fn process_movements(&mut self, dt: f32) {
+        for animal in self.world.animals.iter_mut().filter(|animal| animal.is_alive()) {
+            animal.move_forward(&self.config, dt, self.world.width, self.world.height);
+        }
+    }
+
+    fn process_spacing(&mut self, dt: f32) {
+        if self.config.spacing_reward == 0.0 {
+            return;
+        }
+
+        let positions: Vec<_> = self.world.animals.iter().map(Animal::position).collect();
+
+        for (id, animal) in self.world.animals.iter_mut().enumerate() {
+            if !animal.is_alive() {
+                continue;
+            }
+
+            let nearest = positions
+                .iter()
+                .enumerate()
+                .filter(|(other_id, _)| *other_id != id)
+                .map(|(_, other)| na::distance(&animal.position, other))
+                .fold(None, |nearest: Option<f32>, distance| {
+                    Some(nearest.map_or(distance, |nearest| nearest.min(distance)))
+                });
+
+            if let Some(nearest) = nearest {
+                animal.spacing += nearest * dt;
+                animal.spacing_time += dt;
+            }
+        }
+    }
+
+    fn process_energy(&mut self, dt: f32) {
         for animal in &mut self.world.animals {
-            animal.position += animal.rotation * na::Vector2::new(animal.speed, 0.0);
+            if animal.is_alive() {
+                animal.age += dt;
+            }
 
-            animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
-            animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+            self.config.energy_model.drain(animal, dt);
         }
     }
-}
 
-impl World {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        let animals = (0..50)
-            .map(|_| Animal::random(rng))
+    fn evolve(&mut self, rng: &mut dyn RngCore) {
+        use ga::Individual;
+
+        self.age = 0;
+
+        let current_population: Vec<_> = self
+            .world
+            .animals
+            .iter()
+            .map(|animal| AnimalIndividual::from_animal(&self.config, animal))
             .collect();
 
-        let foods = (0..60)
-            .map(|_| Food::random(rng))
+        let best = current_population
+            .iter()
+            // Picks the first of equally fit individuals, same as elitism
+            .reduce(|best, individual| {
+                if individual.fitness() > best.fitness() { individual } else { best }
+            })
+            .expect("got an empty population");
+
+        self.fitness_history.push(best.fitness().max(0.0));
+
+        let fitness: Vec<_> = current_population.iter().map(Individual::fitness).collect();
+
+        self.statistics_history
+            .push(GenerationStatistics::new(self.generation, &fitness));
+
+        self.best_chromosomes.push(best.chromosome().clone());
+
+        let evolved_population = self.ga.evolve(rng, &current_population);
+
+        let world = &mut self.world;
+
+        world.animals = evolved_population
+            .into_iter()
+            .map(|individual| {
+                let mut animal = individual.into_animal(&self.config, rng);
+                animal.position.x *= world.width;
+                animal.position.y *= world.height;
+                animal
+            })
             .collect();
-        Self { animals, foods }
+
+        self.reset_environment(rng);
+        self.generation += 1;
     }
 
-    pub fn animals(&self) -> &[Animal] {
-        &self.animals
+    /// Places the animals and the foods for a new generation.
+    fn reset_environment(&mut self, rng: &mut dyn RngCore) {
+        self.environment_rng = self.config.environment_seed.map(ChaCha8Rng::seed_from_u64);
+
+        let world = &mut self.world;
+
+        if let Some(environment_rng) = &mut self.environment_rng {
+            for animal in &mut world.animals {
+                animal.position = world::random_position(environment_rng, world.width, world.height);
+            }
+        }
+
+        let rng = environment_rng(&mut self.environment_rng, rng);
+
+        self.config.spawn_layout.arrange(
+            &mut world.animals,
+            self.config.min_spawn_distance,
+            world.width,
+            world.height,
+            rng
+        );
+
+        self.config.food_layout.arrange(&mut world.foods, world.width, world.height, rng);
     }
+}
 
-    pub fn foods(&self) -> &[Food] {
-        &self.foods
+/// Returns the environment's generator, if there's one, or the given one.
+fn environment_rng<'a>(
+    environment_rng: &'a mut Option<ChaCha8Rng>,
+    rng: &'a mut dyn RngCore,
+) -> &'a mut dyn RngCore {
+    match environment_rng {
+        Some(environment_rng) => environment_rng,
+        None => rng,
     }
 }
 
-impl Animal {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        Self { 
-            position: rng.gen(),
-            rotation: rng.gen(),
-            speed: 0.002
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    mod generation_length {
+        use super::*;
+
+        #[test]
+        fn evolves_once_per_generation_length_steps() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { generation_length: 100, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            for generation in 0..3 {
+                for _ in 0..99 {
+                    sim.step(&mut rng);
+                }
+
+                assert_eq!(sim.generation(), generation);
+
+                sim.step(&mut rng);
+
+                assert_eq!(sim.generation(), generation + 1);
+            }
         }
     }
-     pub fn position(&self) -> na::Point2<f32> {
-         self.position
-     }
 
-    pub fn rotation(&self) -> na::Rotation2<f32>  {
-        self.rotation
+    mod cull {
+        use super::*;
+
+        #[test]
+        fn replaces_only_the_weakest_brains() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::random(&mut rng);
+
+            for (id, animal) in sim.world.animals.iter_mut().enumerate() {
+                animal.satiation = id;
+            }
+
+            let before: Vec<_> = sim.world().animals().iter().map(Animal::brain_weights).collect();
+            let half = before.len() / 2;
+
+            sim.cull(&mut rng, 0.5);
+
+            for (id, (animal, before)) in sim.world().animals().iter().zip(&before).enumerate() {
+                assert_eq!(animal.brain_weights() == *before, id >= half, "animal {}", id);
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "cull fraction must be within [0, 1]")]
+        fn rejects_fraction_outside_of_unit_range() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            Simulation::random(&mut rng).cull(&mut rng, 1.5);
+        }
     }
-}
 
-impl Food {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        Self {
-            position: rng.gen()
+    mod from_chromosomes_with_random {
+        use super::*;
+
+        fn seeded_count(random_fraction: f32) -> usize {
+            let genes = Simulation::random(&mut ChaCha8Rng::seed_from_u64(0))
+                .world()
+                .animals()[0]
+                .brain_weights()
+                .len();
+
+            let chromosomes: Vec<ga::Chromosome> = vec![
+                std::iter::repeat_n(0.5, genes).collect(),
+                std::iter::repeat_n(-0.5, genes).collect(),
+            ];
+
+            let sim = Simulation::from_chromosomes_with_random(
+                Config::default(),
+                &chromosomes,
+                random_fraction,
+                1,
+            );
+
+            sim.world()
+                .animals()
+                .iter()
+                .filter(|animal| {
+                    chromosomes.iter().any(|chromosome| animal.brain_weights() == chromosome.genes)
+                })
+                .count()
+        }
+
+        #[test]
+        fn mixes_seeded_and_random_brains() {
+            assert_eq!(seeded_count(0.2), 40);
+            assert_eq!(seeded_count(0.0), 50);
+            assert_eq!(seeded_count(1.0), 0);
+        }
+
+        #[test]
+        #[should_panic(expected = "random fraction must be within [0, 1]")]
+        fn rejects_fraction_outside_of_unit_range() {
+            Simulation::from_chromosomes_with_random(Config::default(), &[], -0.1, 1);
+        }
+
+        #[test]
+        #[should_panic(expected = "at least one chromosome is required")]
+        fn rejects_missing_chromosomes() {
+            Simulation::from_chromosomes_with_random(Config::default(), &[], 0.5, 1);
+        }
+    }
+
+    mod max_speed_and_turn {
+        use super::*;
+
+        #[test]
+        fn limit_maxed_out_brains() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { max_speed: 0.003, max_turn: 0.1, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            for animal in &mut sim.world.animals {
+                let weights = vec![10.0; animal.brain_weights().len()];
+                animal.brain = Brain::from_weights(&sim.config, &animal.eye, weights).unwrap();
+            }
+
+            for _ in 0..20 {
+                let before = sim.world.animals.clone();
+
+                sim.step(&mut rng);
+
+                for (before, after) in before.iter().zip(&sim.world.animals) {
+                    let turn = after.rotation.angle() - before.rotation.angle();
+                    let turn = turn.sin().atan2(turn.cos());
+
+                    assert!(after.distance - before.distance <= 0.003 + 1e-6);
+                    assert!(turn.abs() <= 0.1 + 1e-5, "turned by {}", turn);
+                }
+            }
         }
     }
-    pub fn position(&self) -> na::Point2<f32> {
-        self.position
+
+    mod fitness_decay {
+        use super::*;
+
+        /// Fitness of an animal that eats once, after `delay` steps out of
+        /// 100.
+        fn fitness(delay: usize) -> f32 {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { fitness_decay: 0.01, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            sim.world.animals.truncate(1);
+            sim.world.foods.truncate(1);
+
+            for step in 0..100 {
+                sim.world.foods[0].position = if step == delay {
+                    sim.world.animals[0].position
+                } else {
+                    na::Point2::new(-1.0, -1.0)
+                };
+
+                sim.process_collisions(&mut rng, 1.0);
+            }
+
+            assert_eq!(sim.world.animals[0].satiation(), 1);
+            sim.config.fitness(&sim.world.animals[0])
+        }
+
+        #[test]
+        fn later_eater_scores_higher() {
+            assert!(fitness(80) > fitness(20));
+            approx::assert_relative_eq!(fitness(99), 1.0);
+            approx::assert_relative_eq!(fitness(0), 0.99f32.powi(99), max_relative = 1e-4);
+        }
     }
-}
\ No newline at end of file
+
+    mod targets {
+        use super::*;
+
+        fn targets(foods: &[(f32, f32)]) -> Vec<Option<usize>> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::random(&mut rng);
+
+            sim.world.animals.truncate(1);
+            sim.world.animals[0].position = na::Point2::new(0.5, 0.5);
+            sim.world.animals[0].rotation = na::Rotation2::new(0.0);
+
+            sim.world.foods = foods
+                .iter()
+                .map(|&(x, y)| Food { position: na::Point2::new(x, y) })
+                .collect();
+
+            sim.process_brains(&mut rng, 1.0);
+            sim.targets()
+        }
+
+        #[test]
+        fn targets_the_only_food_in_view() {
+            // Out of range, behind the animal, and right in front of it
+            let foods = [(0.9, 0.9), (0.4, 0.5), (0.6, 0.5)];
+
+            assert_eq!(targets(&foods), vec![Some(2)]);
+        }
+
+        #[test]
+        fn targets_the_nearest_food_in_view() {
+            assert_eq!(targets(&[(0.7, 0.5), (0.6, 0.52)]), vec![Some(1)]);
+        }
+
+        #[test]
+        fn targets_nothing_without_food_in_view() {
+            assert_eq!(targets(&[(0.4, 0.5)]), vec![None]);
+        }
+    }
+
+    mod heading_errors {
+        use super::*;
+
+        fn heading_errors(config: Config, weight: f32) -> Vec<f32> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            for animal in &mut sim.world.animals {
+                let weights = vec![weight; animal.brain_weights().len()];
+                animal.brain = Brain::from_weights(&sim.config, &animal.eye, weights).unwrap();
+            }
+
+            sim.step(&mut rng);
+            sim.heading_errors()
+        }
+
+        #[test]
+        fn straight_ahead_has_no_error() {
+            for error in heading_errors(Config::default(), 0.0) {
+                approx::assert_abs_diff_eq!(error, 0.0, epsilon = 1e-5);
+            }
+        }
+
+        #[test]
+        fn limited_turns_have_errors() {
+            let config = Config { max_turn: 0.1, ..Default::default() };
+
+            for error in heading_errors(config, 10.0) {
+                assert!(error.abs() > 0.1, "got {}", error);
+            }
+        }
+    }
+
+    mod world_packed {
+        use super::*;
+
+        #[test]
+        fn unpacks_into_the_world() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let sim = Simulation::random(&mut rng);
+
+            let packed = sim.world_packed();
+            let (count, animals) = packed.split_first().unwrap();
+
+            assert_eq!(*count as usize, sim.world().animals().len());
+            assert_eq!(animals.len(), 3 * sim.world().animals().len());
+
+            for (packed, animal) in animals.chunks(3).zip(sim.world().animals()) {
+                assert_eq!(packed[0], animal.position().x);
+                assert_eq!(packed[1], animal.position().y);
+                assert_eq!(packed[2], animal.rotation().angle());
+            }
+        }
+    }
+
+    mod gains {
+        use super::*;
+
+        fn distance(speed_gain: f32) -> f32 {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { max_speed: 1.0, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            sim.set_speed_gain(speed_gain).unwrap();
+
+            sim.step(&mut rng);
+            sim.world().animals().iter().map(Animal::distance).sum()
+        }
+
+        #[test]
+        fn doubling_speed_gain_doubles_displacement() {
+            approx::assert_relative_eq!(distance(2.0), 2.0 * distance(1.0), max_relative = 1e-4);
+        }
+
+        #[test]
+        fn rejects_invalid_gains() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::random(&mut rng);
+
+            assert_eq!(sim.set_speed_gain(0.0).unwrap_err().field, "speed_gain");
+            assert_eq!(sim.set_rotation_gain(f32::NAN).unwrap_err().field, "rotation_gain");
+            assert_eq!(sim.config().speed_gain, 1.0);
+        }
+    }
+
+    mod step_dt {
+        use super::*;
+
+        /// Creates a simulation with a single animal that always dashes
+        /// straight ahead (already at full speed), and no food to distract
+        /// it.
+        fn dashing_sim() -> Simulation {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { action_selection: ActionSelection::Argmax, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            sim.world.animals.truncate(1);
+            sim.world.animals[0].speed = SPEED_MAX;
+            sim.world.foods.clear();
+
+            let outputs = Action::ALL.map(|action| if action == Action::Dash { 1.0 } else { 0.0 });
+            set_constant_brain(&mut sim, 0, &outputs);
+            sim
+        }
+
+        /// Gives given animal a brain that responds with `outputs` no matter
+        /// what it sees.
+        fn set_constant_brain(sim: &mut Simulation, animal: usize, outputs: &[f32]) {
+            let topology = sim.world.animals[animal].brain_topology();
+            let weights = testing::constant_brain(&topology, outputs);
+
+            sim.set_animal_brain(animal, weights).unwrap();
+        }
+
+        #[test]
+        fn half_steps_cover_the_same_distance_as_a_full_step() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut halved = dashing_sim();
+            halved.step_dt(&mut rng, 0.5);
+            halved.step_dt(&mut rng, 0.5);
+
+            let mut full = dashing_sim();
+            full.step_dt(&mut rng, 1.0);
+
+            let (halved, full) = (&halved.world.animals[0], &full.world.animals[0]);
+
+            approx::assert_relative_eq!(halved.distance(), full.distance());
+            approx::assert_relative_eq!(halved.position().x, full.position().x, epsilon = 1e-6);
+            approx::assert_relative_eq!(halved.position().y, full.position().y, epsilon = 1e-6);
+        }
+
+        #[test]
+        fn half_steps_end_close_to_a_full_step() {
+            let mut halved = Simulation::random(&mut ChaCha8Rng::from_seed(Default::default()));
+
+            for animal in 0..halved.world.animals.len() {
+                set_constant_brain(&mut halved, animal, &[SPEED_MAX / 2.0, ROTATION_ACCEL / 2.0]);
+            }
+
+            let mut full = halved.clone();
+
+            let mut rng = ChaCha8Rng::seed_from_u64(1);
+            halved.step_dt(&mut rng, 0.5);
+            halved.step_dt(&mut rng, 0.5);
+
+            let mut rng = ChaCha8Rng::seed_from_u64(1);
+            full.step(&mut rng);
+
+            for (halved, full) in halved.world.animals.iter().zip(&full.world.animals) {
+                // Within half of what an animal can move or accelerate during
+                // a step
+                approx::assert_relative_eq!(halved.position().x, full.position().x, epsilon = SPEED_MAX / 2.0);
+                approx::assert_relative_eq!(halved.position().y, full.position().y, epsilon = SPEED_MAX / 2.0);
+                approx::assert_relative_eq!(halved.speed, full.speed, epsilon = SPEED_MAX / 2.0);
+
+                let turn = halved.rotation.angle() - full.rotation.angle();
+                approx::assert_relative_eq!(turn.sin().atan2(turn.cos()), 0.0, epsilon = 1e-5);
+            }
+        }
+
+        #[test]
+        fn long_steps_dont_skip_foods() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = dashing_sim();
+
+            let animal = &sim.world.animals[0];
+            let ahead = animal.rotation * na::Vector2::new(SPEED_MAX * 5.0, 0.0);
+
+            sim.world.foods.push(Food { position: animal.position + ahead });
+            sim.step_dt(&mut rng, 10.0);
+
+            assert_eq!(sim.world.animals[0].satiation, 1);
+        }
+
+        #[test]
+        fn ages_by_whole_steps() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = dashing_sim();
+
+            sim.step_dt(&mut rng, 0.5);
+            assert_eq!(sim.age(), 0);
+
+            sim.step_dt(&mut rng, 0.5);
+            assert_eq!(sim.age(), 1);
+
+            sim.step_dt(&mut rng, 2.5);
+            assert_eq!(sim.age(), 3);
+        }
+
+        #[test]
+        #[should_panic(expected = "dt must be positive")]
+        fn rejects_non_positive_dt() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            dashing_sim().step_dt(&mut rng, 0.0);
+        }
+    }
+
+    mod spacing_reward {
+        use super::*;
+
+        fn mean_fitness(spread: f32) -> f32 {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { spacing_reward: 1.0, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            let count = sim.world.animals.len();
+
+            for (id, animal) in sim.world.animals.iter_mut().enumerate() {
+                let center = na::Point2::new(0.5, 0.5);
+                let position = spawn_layout::grid_position(id, count, 1.0, 1.0);
+
+                animal.position = center + (position - center) * spread;
+            }
+
+            sim.process_spacing(1.0);
+
+            let animals = sim.world().animals();
+            animals.iter().map(|animal| sim.config.fitness(animal)).sum::<f32>() / count as f32
+        }
+
+        #[test]
+        fn dispersed_animals_score_higher() {
+            assert!(mean_fitness(1.0) > mean_fitness(0.1));
+        }
+
+        #[test]
+        fn is_not_tracked_by_default() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::random(&mut rng);
+
+            sim.step(&mut rng);
+
+            assert!(sim.world().animals().iter().all(|animal| animal.mean_spacing() == 0.0));
+        }
+    }
+
+    mod age_penalty {
+        use super::*;
+
+        #[test]
+        fn evolves_despite_negative_fitness() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { age_penalty: 1.0, generation_length: 10, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            sim.train(&mut rng);
+
+            assert_eq!(sim.generation(), 1);
+        }
+    }
+
+    mod environment_seed {
+        use super::*;
+
+        fn foods(sim: &Simulation) -> Vec<na::Point2<f32>> {
+            sim.world().foods().iter().map(Food::position).collect()
+        }
+
+        #[test]
+        fn keeps_food_placement_while_brains_evolve() {
+            let config = Config {
+                generation_length: 100,
+                environment_seed: Some(1234),
+                ..Default::default()
+            };
+
+            let mut rng_a = ChaCha8Rng::seed_from_u64(1);
+            let mut rng_b = ChaCha8Rng::seed_from_u64(2);
+            let mut a = Simulation::with_config(&mut rng_a, config.clone());
+            let mut b = Simulation::with_config(&mut rng_b, config);
+
+            let initial_foods = foods(&a);
+
+            for _ in 0..3 {
+                assert_eq!(foods(&a), initial_foods);
+                assert_eq!(foods(&b), initial_foods);
+                assert_ne!(a.world().animals()[0].brain_weights(), b.world().animals()[0].brain_weights());
+
+                a.train(&mut rng_a);
+                b.train(&mut rng_b);
+            }
+        }
+    }
+
+    mod eat_radius {
+        use super::*;
+
+        fn satiation_after_collision(distance: f32) -> usize {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { eat_radius: 0.05, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            sim.world.animals.truncate(1);
+            sim.world.foods.truncate(1);
+            sim.world.animals[0].position = na::Point2::new(0.5, 0.5);
+            sim.world.foods[0].position = na::Point2::new(0.5 + distance, 0.5);
+
+            sim.process_collisions(&mut rng, 1.0);
+            sim.world.animals[0].satiation()
+        }
+
+        #[test]
+        fn eats_only_within_radius() {
+            assert_eq!(satiation_after_collision(0.049), 1);
+            assert_eq!(satiation_after_collision(0.051), 0);
+        }
+    }
+
+    mod elitism {
+        use super::*;
+
+        #[test]
+        fn keeps_the_best_brain_across_generations() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { generation_length: 100, elitism: 1, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            for _ in 0..3 {
+                sim.train(&mut rng);
+
+                let best: Vec<f32> = sim.best_chromosomes().last().unwrap().iter().copied().collect();
+
+                assert_eq!(sim.world().animals()[0].brain_weights(), best);
+            }
+        }
+    }
+
+    mod best_chromosomes {
+        use super::*;
+
+        #[test]
+        fn keeps_one_chromosome_per_generation() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config { generation_length: 100, ..Default::default() };
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            for _ in 0..3 {
+                sim.train(&mut rng);
+            }
+
+            let genes = sim.world().animals()[0].brain_weights().len();
+
+            assert_eq!(sim.best_chromosomes().len(), 3);
+
+            for chromosome in sim.best_chromosomes() {
+                assert_eq!(chromosome.len(), genes);
+            }
+        }
+    }
+
+    mod statistics_history {
+        use super::*;
+
+        #[test]
+        fn keeps_only_the_most_recent_generations() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config {
+                generation_length: 1,
+                statistics_history_len: 3,
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::with_config(&mut rng, config);
+
+            for _ in 0..5 {
+                sim.step(&mut rng);
+            }
+
+            let generations: Vec<_> = sim
+                .statistics_history()
+                .iter()
+                .map(|stats| stats.generation)
+                .collect();
+
+            assert_eq!(generations, vec![2, 3, 4]);
+        }
+    }
+
+    mod fitness_history {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut sim = Simulation::random(&mut rng);
+
+            assert!(sim.fitness_history().is_empty());
+
+            for _ in 0..3 {
+                sim.train(&mut rng);
+            }
+
+            assert_eq!(sim.generation(), 3);
+            assert_eq!(sim.fitness_history().len(), sim.generation());
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::*;
+
+/// Describes how the brain's outputs are turned into movement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MovementModel {
+    /// The outputs change the speed and the rotation directly.
+    #[default]
+    SpeedRotation,
+
+    /// The outputs are the speeds of the left and the right wheel; equal
+    /// speeds move the animal straight ahead, different ones turn it.
+    DifferentialDrive,
+}
+
+impl MovementModel {
+    /// Number of brain outputs this model consumes.
+    pub fn outputs(&self) -> usize {
+        match self {
+            Self::SpeedRotation => 2,
+            Self::DifferentialDrive => 2,
+        }
+    }
+
+    pub(crate) fn apply(&self, animal: &mut Animal, response: &[f32]) {
+        assert_eq!(response.len(), self.outputs());
+
+        match self {
+            Self::SpeedRotation => {
+                let speed = response[0].clamp(-SPEED_ACCEL, SPEED_ACCEL);
+                let rotation = response[1].clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
+
+                animal.speed = (animal.speed + speed).clamp(SPEED_MIN, SPEED_MAX);
+                animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
+            }
+
+            Self::DifferentialDrive => {
+                let left = response[0].clamp(0.0, 1.0);
+                let right = response[1].clamp(0.0, 1.0);
+
+                animal.speed = ((left + right) / 2.0 * SPEED_MAX).clamp(SPEED_MIN, SPEED_MAX);
+                animal.rotation = na::Rotation2::new(
+                    animal.rotation.angle() + (right - left) * ROTATION_ACCEL
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn animal() -> Animal {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&Config::default(), &mut rng);
+
+        animal.rotation = na::Rotation2::new(0.0);
+        animal
+    }
+
+    mod differential_drive {
+        use super::*;
+
+        #[test]
+        fn equal_wheels_move_straight() {
+            let mut animal = animal();
+            let rotation = animal.rotation.angle();
+
+            MovementModel::DifferentialDrive.apply(&mut animal, &[0.5, 0.5]);
+
+            approx::assert_relative_eq!(animal.rotation.angle(), rotation);
+            approx::assert_relative_eq!(animal.speed, 0.5 * SPEED_MAX);
+        }
+
+        #[test]
+        fn unequal_wheels_turn() {
+            let mut animal = animal();
+            let rotation = animal.rotation.angle();
+
+            MovementModel::DifferentialDrive.apply(&mut animal, &[0.2, 0.8]);
+            assert!(animal.rotation.angle() > rotation);
+
+            let rotation = animal.rotation.angle();
+
+            MovementModel::DifferentialDrive.apply(&mut animal, &[0.8, 0.2]);
+            assert!(animal.rotation.angle() < rotation);
+        }
+    }
+}
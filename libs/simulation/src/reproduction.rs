@@ -0,0 +1,86 @@
+use crate::*;
+
+/// Describes how the offspring's brains are made out of their parents'.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Reproduction {
+    /// Two parents' chromosomes are crossed over, then mutated.
+    #[default]
+    Sexual,
+
+    /// A single parent's chromosome is cloned, then mutated.
+    Asexual,
+}
+
+impl Reproduction {
+    pub(crate) fn genetic_algorithm(
+        &self,
+        elitism: usize,
+    ) -> ga::GeneticAlgorithm<ga::RouletteWheelSelection> {
+        let selection = ga::RouletteWheelSelection::new();
+        let mutation = ga::GaussianMutation::new(0.01, 0.3);
+
+        let ga = match self {
+            Self::Sexual => {
+                ga::GeneticAlgorithm::new(selection, ga::UniformCrossover::new(), mutation)
+            }
+
+            Self::Asexual => {
+                ga::GeneticAlgorithm::new(selection, ga::CloneCrossover::new(), mutation)
+            }
+        };
+
+        ga.with_elitism(elitism)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    /// For each of the new generation's brains, the fewest genes it differs
+    /// in from any of the previous generation's brains.
+    fn closest_parent_differences(reproduction: Reproduction) -> Vec<usize> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let config = Config { reproduction, generation_length: 1, ..Default::default() };
+        let mut sim = Simulation::with_config(&mut rng, config);
+
+        let parents: Vec<_> = sim.world().animals().iter().map(Animal::brain_weights).collect();
+
+        sim.step(&mut rng);
+
+        sim.world()
+            .animals()
+            .iter()
+            .map(|child| {
+                let child = child.brain_weights();
+
+                parents
+                    .iter()
+                    .map(|parent| child.iter().zip(parent).filter(|(a, b)| a != b).count())
+                    .min()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn asexual_offspring_are_mutated_clones() {
+        let genes = {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            Simulation::random(&mut rng).world().animals()[0].brain_weights().len()
+        };
+
+        // With the mutation chance of 1%, barely any genes differ
+        for differences in closest_parent_differences(Reproduction::Asexual) {
+            assert!(differences < genes / 10, "{} of {} genes differ", differences, genes);
+        }
+    }
+
+    #[test]
+    fn sexual_offspring_mix_their_parents() {
+        let differences = closest_parent_differences(Reproduction::Sexual);
+
+        assert!(differences.iter().filter(|&&differences| differences > 10).count() > 25);
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+/// Keeps the last `capacity` values pushed into it, dropping the oldest one
+/// whenever a new one doesn't fit; never grows past its capacity.
+#[derive(Clone, Debug)]
+pub(crate) struct RingBuffer<T> {
+    capacity: usize,
+    values: VecDeque<T>,
+}
+
+impl<T> RingBuffer<T> {
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "ring buffer's capacity must be positive");
+
+        Self { capacity, values: VecDeque::with_capacity(capacity) }
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+
+        self.values.push_back(value);
+    }
+
+    /// Iterates over the values, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.values.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_most_recent_values_in_order() {
+        let mut buffer = RingBuffer::new(3);
+
+        for value in 0..5 {
+            buffer.push(value);
+        }
+
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn keeps_everything_until_full() {
+        let mut buffer = RingBuffer::new(3);
+
+        buffer.push(1);
+        buffer.push(2);
+
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be positive")]
+    fn rejects_zero_capacity() {
+        RingBuffer::<f32>::new(0);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::*;
+
+/// How many times [`SpawnLayout::Random`] tries to find a spot far enough
+/// from the others before settling for the last one it drew.
+const MAX_SPAWN_ATTEMPTS: usize = 100;
+
+/// Describes where the animals are placed at the beginning of each
+/// generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpawnLayout {
+    /// Each animal starts at a random position, at least
+    /// [`Config::min_spawn_distance`] away from the others if possible -
+    /// in crowded worlds, some animals may end up closer.
+    #[default]
+    Random,
+
+    /// Animals start in the centers of a regular grid's cells, row by row.
+    Grid,
+
+    /// Animals start evenly spaced on a circle around the world's center.
+    Circle,
+}
+
+impl SpawnLayout {
+    /// Moves the animals into this layout; [`SpawnLayout::Random`] leaves
+    /// them where they were spawned, except for moving apart the ones
+    /// closer than `min_distance` to each other.
+    pub(crate) fn arrange(
+        &self,
+        animals: &mut [Animal],
+        min_distance: f32,
+        width: f32,
+        height: f32,
+        rng: &mut dyn RngCore
+    ) {
+        if *self == Self::Random {
+            space_out(animals, min_distance, width, height, rng);
+            return;
+        }
+
+        let count = animals.len();
+
+        for (id, animal) in animals.iter_mut().enumerate() {
+            if let Some(position) = self.position(id, count, width, height) {
+                animal.position = position;
+            }
+        }
+    }
+
+    fn position(
+        &self,
+        id: usize,
+        count: usize,
+        width: f32,
+        height: f32
+    ) -> Option<na::Point2<f32>> {
+        match self {
+            Self::Random => None,
+
+            Self::Grid => Some(grid_position(id, count, width, height)),
+
+            Self::Circle => {
+                let radius = 0.4 * width.min(height);
+                let angle = 2.0 * PI * id as f32 / count as f32;
+
+                Some(na::Point2::new(
+                    width / 2.0 + radius * angle.cos(),
+                    height / 2.0 + radius * angle.sin(),
+                ))
+            }
+        }
+    }
+}
+
+/// Redraws the positions of animals that lie closer than `min_distance` to
+/// any of the ones before them, giving up after [`MAX_SPAWN_ATTEMPTS`].
+fn space_out(
+    animals: &mut [Animal],
+    min_distance: f32,
+    width: f32,
+    height: f32,
+    rng: &mut dyn RngCore
+) {
+    if min_distance == 0.0 {
+        return;
+    }
+
+    for id in 1..animals.len() {
+        let (placed, rest) = animals.split_at_mut(id);
+        let animal = &mut rest[0];
+
+        let is_crowded = |position: &na::Point2<f32>| {
+            placed
+                .iter()
+                .any(|other| na::distance(&other.position, position) < min_distance)
+        };
+
+        for _ in 0..MAX_SPAWN_ATTEMPTS {
+            if !is_crowded(&animal.position) {
+                break;
+            }
+
+            animal.position = world::random_position(rng, width, height);
+        }
+    }
+}
+
+/// Returns the center of `id`-th cell of a regular grid with `count` cells,
+/// filled row by row.
+pub(crate) fn grid_position(id: usize, count: usize, width: f32, height: f32) -> na::Point2<f32> {
+    let cols = (count as f32).sqrt().ceil() as usize;
+    let rows = count.div_ceil(cols);
+
+    let col = id % cols;
+    let row = id / cols;
+
+    na::Point2::new(
+        (col as f32 + 0.5) * width / cols as f32,
+        (row as f32 + 0.5) * height / rows as f32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn positions(layout: SpawnLayout, seed: u64) -> Vec<na::Point2<f32>> {
+        let config = Config { spawn_layout: layout, ..Default::default() };
+        let world = World::random(&config, &mut ChaCha8Rng::seed_from_u64(seed));
+
+        world.animals().iter().map(Animal::position).collect()
+    }
+
+    mod random {
+        use super::*;
+
+        fn positions(min_spawn_distance: f32) -> Vec<na::Point2<f32>> {
+            let config = Config { min_spawn_distance, ..Default::default() };
+            let world = World::random(&config, &mut ChaCha8Rng::seed_from_u64(0));
+
+            world.animals().iter().map(Animal::position).collect()
+        }
+
+        #[test]
+        fn keeps_animals_apart() {
+            let positions = positions(0.05);
+
+            for (id, a) in positions.iter().enumerate() {
+                for b in &positions[id + 1..] {
+                    assert!(na::distance(a, b) >= 0.05, "{:?} vs {:?}", a, b);
+                }
+            }
+        }
+
+        #[test]
+        fn gives_up_in_crowded_worlds() {
+            // 50 animals can't possibly be this far apart in a unit square
+            assert_eq!(positions(0.9).len(), 50);
+        }
+    }
+
+    mod grid {
+        use super::*;
+
+        #[test]
+        fn places_animals_on_a_lattice_within_bounds() {
+            let positions = positions(SpawnLayout::Grid, 0);
+
+            // 50 animals make a grid of 8 columns and 7 rows
+            let (cols, rows) = (8.0, 7.0);
+
+            for position in &positions {
+                assert!((0.0..=1.0).contains(&position.x));
+                assert!((0.0..=1.0).contains(&position.y));
+
+                let col = position.x * cols - 0.5;
+                let row = position.y * rows - 0.5;
+
+                approx::assert_abs_diff_eq!(col, col.round(), epsilon = 1e-4);
+                approx::assert_abs_diff_eq!(row, row.round(), epsilon = 1e-4);
+            }
+        }
+
+        #[test]
+        fn does_not_depend_on_the_seed() {
+            assert_eq!(positions(SpawnLayout::Grid, 1), positions(SpawnLayout::Grid, 2));
+        }
+    }
+
+    mod circle {
+        use super::*;
+
+        #[test]
+        fn does_not_depend_on_the_seed() {
+            assert_eq!(positions(SpawnLayout::Circle, 1), positions(SpawnLayout::Circle, 2));
+        }
+    }
+}
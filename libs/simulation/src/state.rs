@@ -0,0 +1,321 @@
+use std::fmt;
+
+use crate::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Plain-data snapshot of a [`Simulation`], from which it can be restored
+/// exactly; see [`Simulation::save_state()`].
+///
+/// The configuration is not a part of the state - it's taken from the
+/// simulation the state is loaded into. The environment's generator (see
+/// [`Config::environment_seed`]) is saved along with its position, so food
+/// respawns continue exactly as they would have without the round-trip.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimulationState {
+    pub age: usize,
+    pub step_fraction: f32,
+    pub generation: usize,
+    pub fitness_history: Vec<f32>,
+    pub statistics_history: Vec<GenerationStatistics>,
+    pub best_chromosomes: Vec<Vec<f32>>,
+    pub width: f32,
+    pub height: f32,
+    pub animals: Vec<AnimalState>,
+    pub foods: Vec<[f32; 2]>,
+    pub environment_rng: Option<RngState>,
+}
+
+/// Position of a [`ChaCha8Rng`] within its stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RngState {
+    pub seed: [u8; 32],
+    pub stream: u64,
+    /// Kept as a string, since formats such as JSON can't reliably hold a
+    /// `u128`.
+    pub word_pos: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnimalState {
+    pub position: [f32; 2],
+    /// Cosine and sine of the rotation; stored instead of the angle so that
+    /// restoring doesn't lose precision.
+    pub rotation: [f32; 2],
+    pub speed: f32,
+    pub satiation: usize,
+    pub recent_food: f32,
+    pub distance: f32,
+    pub spacing: f32,
+    pub spacing_time: f32,
+    pub age: f32,
+    pub energy: f32,
+    pub heading_error: f32,
+    pub target: Option<usize>,
+    pub brain: Vec<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StateError {
+    /// Animal's brain doesn't match the simulation's topology.
+    Brain { animal: usize, error: nn::WeightError },
+
+    /// Environment generator's word position is not a number.
+    RngWordPos(String),
+}
+
+impl SimulationState {
+    pub(crate) fn new(sim: &Simulation) -> Self {
+        let world = &sim.world;
+
+        let animals = world
+            .animals
+            .iter()
+            .map(|animal| {
+                let rotation = animal.rotation.matrix();
+
+                AnimalState {
+                    position: [animal.position.x, animal.position.y],
+                    rotation: [rotation[(0, 0)], rotation[(1, 0)]],
+                    speed: animal.speed,
+                    satiation: animal.satiation,
+                    recent_food: animal.recent_food,
+                    distance: animal.distance,
+                    spacing: animal.spacing,
+                    spacing_time: animal.spacing_time,
+                    age: animal.age,
+                    energy: animal.energy,
+                    heading_error: animal.heading_error,
+                    target: animal.target,
+                    brain: animal.brain.weights(),
+                }
+            })
+            .collect();
+
+        let foods = world
+            .foods
+            .iter()
+            .map(|food| [food.position.x, food.position.y])
+            .collect();
+
+        Self {
+            age: sim.age,
+            step_fraction: sim.step_fraction,
+            generation: sim.generation,
+            fitness_history: sim.fitness_history(),
+            statistics_history: sim.statistics_history(),
+            best_chromosomes: sim
+                .best_chromosomes
+                .iter()
+                .map(|chromosome| chromosome.iter().copied().collect())
+                .collect(),
+            width: world.width,
+            height: world.height,
+            animals,
+            foods,
+            environment_rng: sim.environment_rng.as_ref().map(RngState::new),
+        }
+    }
+
+    pub(crate) fn restore(self, sim: &mut Simulation) -> Result<(), StateError> {
+        let environment_rng = match (self.environment_rng, sim.config.environment_seed) {
+            (Some(state), Some(_)) => Some(state.restore()?),
+            (_, seed) => seed.map(ChaCha8Rng::seed_from_u64),
+        };
+
+        let animals = self
+            .animals
+            .into_iter()
+            .enumerate()
+            .map(|(id, state)| {
+                let eye = Eye::from_config(&sim.config);
+
+                let brain = Brain::from_weights(&sim.config, &eye, state.brain)
+                    .map_err(|error| StateError::Brain { animal: id, error })?;
+
+                let [cos, sin] = state.rotation;
+
+                Ok(Animal {
+                    position: na::Point2::new(state.position[0], state.position[1]),
+                    rotation: na::Rotation2::from_matrix_unchecked(
+                        na::Matrix2::new(cos, -sin, sin, cos)
+                    ),
+                    speed: state.speed,
+                    eye,
+                    brain,
+                    satiation: state.satiation,
+                    recent_food: state.recent_food,
+                    distance: state.distance,
+                    spacing: state.spacing,
+                    spacing_time: state.spacing_time,
+                    age: state.age,
+                    energy: state.energy,
+                    intended_heading: sin.atan2(cos),
+                    heading_error: state.heading_error,
+                    target: state.target,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let foods = self
+            .foods
+            .into_iter()
+            .map(|[x, y]| Food { position: na::Point2::new(x, y) })
+            .collect();
+
+        sim.world = World {
+            animals,
+            foods,
+            width: self.width,
+            height: self.height,
+        };
+
+        sim.age = self.age;
+        sim.step_fraction = self.step_fraction;
+        sim.generation = self.generation;
+        sim.environment_rng = environment_rng;
+        sim.fitness_history = RingBuffer::new(FITNESS_HISTORY_LEN);
+
+        for fitness in self.fitness_history {
+            sim.fitness_history.push(fitness);
+        }
+
+        sim.statistics_history = RingBuffer::new(sim.config.statistics_history_len);
+
+        for stats in self.statistics_history {
+            sim.statistics_history.push(stats);
+        }
+
+        sim.best_chromosomes = RingBuffer::new(BEST_CHROMOSOMES_LEN);
+
+        for genes in self.best_chromosomes {
+            sim.best_chromosomes.push(genes.into_iter().collect());
+        }
+
+        Ok(())
+    }
+}
+
+impl RngState {
+    pub fn new(rng: &ChaCha8Rng) -> Self {
+        Self {
+            seed: rng.get_seed(),
+            stream: rng.get_stream(),
+            word_pos: rng.get_word_pos().to_string(),
+        }
+    }
+
+    /// Recreates the rng at the saved position of its stream.
+    pub fn restore(self) -> Result<ChaCha8Rng, StateError> {
+        let word_pos = self
+            .word_pos
+            .parse()
+            .map_err(|_| StateError::RngWordPos(self.word_pos.clone()))?;
+
+        let mut rng = ChaCha8Rng::from_seed(self.seed);
+        rng.set_stream(self.stream);
+        rng.set_word_pos(word_pos);
+
+        Ok(rng)
+    }
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Brain { animal, error } => {
+                write!(f, "animal #{} has an invalid brain: {}", animal, error)
+            }
+            Self::RngWordPos(word_pos) => {
+                write!(f, "invalid rng word position: {}", word_pos)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn restored_simulation_behaves_identically() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+
+        for _ in 0..10 {
+            sim.step(&mut rng);
+        }
+
+        let state = sim.save_state();
+        let mut restored = Simulation::random(&mut ChaCha8Rng::seed_from_u64(1));
+        restored.load_state(state.clone()).unwrap();
+
+        assert_eq!(restored.save_state(), state);
+        assert_eq!(restored.targets(), sim.targets());
+        assert_eq!(restored.heading_errors(), sim.heading_errors());
+
+        let mut restored_rng = rng.clone();
+
+        for _ in 0..10 {
+            sim.step(&mut rng);
+            restored.step(&mut restored_rng);
+        }
+
+        assert_eq!(restored.save_state(), sim.save_state());
+    }
+
+    #[test]
+    fn restores_environment_rng_position() {
+        let config = Config { environment_seed: Some(1234), ..Default::default() };
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::with_config(&mut rng, config.clone());
+
+        for _ in 0..200 {
+            sim.step(&mut rng);
+        }
+
+        let state = sim.save_state();
+        assert!(state.environment_rng.is_some());
+
+        let mut restored = Simulation::with_config(&mut ChaCha8Rng::seed_from_u64(1), config);
+        restored.load_state(state.clone()).unwrap();
+
+        let mut restored_rng = rng.clone();
+
+        for _ in 0..500 {
+            sim.step(&mut rng);
+            restored.step(&mut restored_rng);
+        }
+
+        let eaten = |state: &SimulationState| -> usize {
+            state.animals.iter().map(|animal| animal.satiation).sum()
+        };
+
+        // Otherwise no food has been respawned and the check is moot
+        assert!(eaten(&sim.save_state()) > eaten(&state));
+        assert_eq!(restored.save_state(), sim.save_state());
+    }
+
+    #[test]
+    fn rejects_brains_of_wrong_size() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+
+        let mut state = sim.save_state();
+        state.animals[1].brain.pop();
+
+        assert!(matches!(
+            sim.load_state(state),
+            Err(StateError::Brain { animal: 1, .. })
+        ));
+    }
+}
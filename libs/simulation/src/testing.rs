@@ -0,0 +1,24 @@
+//! Fixtures shared by this crate's tests and those of its dependents, which
+//! get them through the `testing` feature.
+
+/// Brain weights for given topology (see [`crate::Animal::brain_topology()`]) that
+/// make the brain respond with `outputs` no matter what it sees; `outputs`
+/// must not be negative.
+pub fn constant_brain(topology: &[usize], outputs: &[f32]) -> Vec<f32> {
+    assert_eq!(topology.len(), 3, "expected a single hidden layer");
+    assert_eq!(topology[2], outputs.len());
+
+    let (inputs, hidden) = (topology[0], topology[1]);
+
+    // Hidden neurons output 1.0 regardless of the inputs...
+    let hidden_layer = (0..hidden).flat_map(|_| {
+        std::iter::once(1.0).chain(std::iter::repeat_n(0.0, inputs))
+    });
+
+    // ... which each output neuron scales to its output
+    let output_layer = outputs.iter().flat_map(|&output| {
+        std::iter::once(0.0).chain(std::iter::repeat_n(output / hidden as f32, hidden))
+    });
+
+    hidden_layer.chain(output_layer).collect()
+}
@@ -0,0 +1,82 @@
+/// Distance (relative to the eye's range) below which
+/// [`VisionEncoding::InverseSquare`] stops growing, so that a target right
+/// next to the eye doesn't produce an infinite signal.
+const INVERSE_SQUARE_NEAR: f32 = 0.1;
+
+/// Describes how strongly an eye cell reacts to a target, depending on how
+/// far away the target is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VisionEncoding {
+    /// Falls off linearly, from `1.0` right next to the eye to `0.0` at the
+    /// edge of its range.
+    #[default]
+    Linear,
+
+    /// Falls off with the square of the distance, so that near targets
+    /// dominate; targets within a tenth of the range all produce `1.0`.
+    InverseSquare,
+
+    /// Every target within range produces `1.0`, regardless of distance.
+    Binary,
+}
+
+impl VisionEncoding {
+    /// Signal produced by a target at `dist` from an eye seeing up to
+    /// `fov_range` away; `dist` has to lie within `[0, fov_range)`.
+    pub(crate) fn activation(&self, dist: f32, fov_range: f32) -> f32 {
+        match self {
+            Self::Linear => (fov_range - dist) / fov_range,
+
+            Self::InverseSquare => {
+                let near = INVERSE_SQUARE_NEAR * fov_range;
+                (near / dist.max(near)).powi(2)
+            }
+
+            Self::Binary => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    fn vision(encoding: VisionEncoding, food: na::Point2<f32>) -> f32 {
+        let eye = Eye::new(1.0, FRAC_PI_2, 1).with_encoding(encoding);
+
+        eye.process_vision(
+            na::Point2::new(0.0, 0.0),
+            na::Rotation2::new(0.0),
+            &[Food { position: food }],
+        )[0]
+    }
+
+    #[test]
+    fn inverse_square_favors_close_objects() {
+        let near = vision(VisionEncoding::InverseSquare, na::Point2::new(0.2, 0.0));
+        let far = vision(VisionEncoding::InverseSquare, na::Point2::new(0.4, 0.0));
+
+        // Twice as far away makes for a quarter of the signal
+        approx::assert_relative_eq!(near, 0.25, epsilon = 1e-5);
+        approx::assert_relative_eq!(near / far, 4.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn inverse_square_saturates_near_the_eye() {
+        let vision = vision(VisionEncoding::InverseSquare, na::Point2::new(0.01, 0.0));
+        approx::assert_relative_eq!(vision, 1.0);
+    }
+
+    #[test]
+    fn linear_falls_off_towards_the_range() {
+        let vision = vision(VisionEncoding::Linear, na::Point2::new(0.25, 0.0));
+        approx::assert_relative_eq!(vision, 0.75, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn binary_ignores_the_distance() {
+        assert_eq!(vision(VisionEncoding::Binary, na::Point2::new(0.9, 0.0)), 1.0);
+        assert_eq!(vision(VisionEncoding::Binary, na::Point2::new(1.1, 0.0)), 0.0);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::*;
+
+#[derive(Clone, Debug)]
+pub struct World {
+    pub(crate) animals: Vec<Animal>,
+    pub(crate) foods: Vec<Food>,
+    pub(crate) width: f32,
+    pub(crate) height: f32,
+}
+
+impl World {
+    pub fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
+        let mut animals: Vec<_> = (0..50)
+            .map(|_| Animal::random(config, rng))
+            .collect();
+
+        config.spawn_layout.arrange(&mut animals, config.min_spawn_distance, 1.0, 1.0, rng);
+
+        let mut foods = vec![Food { position: na::Point2::origin() }; config.food_count];
+
+        config.food_layout.arrange(&mut foods, 1.0, 1.0, rng);
+
+        Self { animals, foods, width: 1.0, height: 1.0 }
+    }
+
+    pub fn animals(&self) -> &[Animal] {
+        &self.animals
+    }
+
+    pub fn foods(&self) -> &[Food] {
+        &self.foods
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Changes the world's dimensions, rescaling everyone's positions so
+    /// that they keep their relative placement within the new bounds.
+    ///
+    /// Everything else stays in world units - the speeds, the eyes' range,
+    /// the eating radius - so e.g. in a world twice as wide, animals take
+    /// twice as long to cross it and see a smaller part of it.
+    pub fn resize(&mut self, width: f32, height: f32) {
+        assert!(width.is_finite() && width > 0.0);
+        assert!(height.is_finite() && height > 0.0);
+
+        let scale_x = width / self.width;
+        let scale_y = height / self.height;
+
+        let positions = self
+            .animals
+            .iter_mut()
+            .map(|animal| &mut animal.position)
+            .chain(self.foods.iter_mut().map(|food| &mut food.position));
+
+        for position in positions {
+            position.x = (position.x * scale_x).clamp(0.0, width);
+            position.y = (position.y * scale_y).clamp(0.0, height);
+        }
+
+        self.width = width;
+        self.height = height;
+    }
+}
+
+/// Returns a random point within a world of given dimensions.
+pub(crate) fn random_position(rng: &mut dyn RngCore, width: f32, height: f32) -> na::Point2<f32> {
+    let position: na::Point2<f32> = rng.gen();
+
+    na::Point2::new(position.x * width, position.y * height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    mod resize {
+        use super::*;
+
+        #[test]
+        fn keeps_everyone_within_bounds() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut world = World::random(&Config::default(), &mut rng);
+
+            world.resize(0.5, 0.25);
+
+            assert_eq!(world.width(), 0.5);
+            assert_eq!(world.height(), 0.25);
+
+            let positions = world
+                .animals()
+                .iter()
+                .map(Animal::position)
+                .chain(world.foods().iter().map(Food::position));
+
+            for position in positions {
+                assert!((0.0..=0.5).contains(&position.x));
+                assert!((0.0..=0.25).contains(&position.y));
+            }
+        }
+
+        #[test]
+        fn keeps_distances_in_world_units() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut world = World::random(&Config::default(), &mut rng);
+            let fov_range = world.animals()[0].eye.fov_range();
+
+            world.resize(4.0, 4.0);
+
+            // Animals see the same distance, i.e. a smaller part of the world
+            assert_eq!(world.animals()[0].eye.fov_range(), fov_range);
+        }
+
+        #[test]
+        #[should_panic]
+        fn rejects_non_positive_dimensions() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut world = World::random(&Config::default(), &mut rng);
+
+            world.resize(0.0, 1.0);
+        }
+    }
+}